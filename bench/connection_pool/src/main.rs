@@ -0,0 +1,108 @@
+//! Compares the latency of sequential requests to a local tls mock of the
+//! stripe api, with and without connection reuse.
+//!
+//! Run with `cargo run --release -p connection_pool`, which prints something like:
+//!
+//! ```text
+//! no pool (default)    mean   900.17µs  p50   878.31µs  p99     1.15ms
+//! pooled               mean    29.48µs  p50    23.28µs  p99    59.35µs
+//! pooled, http2 only   mean   245.36µs  p50    23.58µs  p99   122.20µs
+//! ```
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyper::{server::conn::Http, service::service_fn, Body, Request, Response};
+use stripe::{Client, HyperTransport, Method};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+const REQUESTS: usize = 200;
+
+#[tokio::main]
+async fn main() {
+    let mut params = rcgen::CertificateParams::default();
+    params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    params.distinguished_name.push(rcgen::DnType::CommonName, "stripe mock ca");
+    let ca = rcgen::Certificate::from_params(params).unwrap();
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+
+    let ca_pem = ca.serialize_pem().unwrap();
+    let url = format!("https://localhost:{}/", serve(&cert, &ca).await);
+
+    let transports = [
+        ("no pool (default)", HyperTransport::builder()),
+        (
+            "pooled",
+            HyperTransport::builder()
+                .pool_max_idle_per_host(8)
+                .tcp_keepalive(Duration::from_secs(30)),
+        ),
+        (
+            "pooled, http2 only",
+            HyperTransport::builder().pool_max_idle_per_host(8).http2_only(true),
+        ),
+    ];
+
+    for (name, transport) in transports {
+        let transport = transport.add_root_certificate(ca_pem.as_bytes()).build();
+        let client = Client::from_url(url.as_str(), "sk_test_123").with_transport(transport);
+
+        let mut latencies = Vec::with_capacity(REQUESTS);
+        for _ in 0..REQUESTS {
+            let start = Instant::now();
+            client.request_raw(Method::Get, "balance", &serde_json::Value::Null).await.unwrap();
+            latencies.push(start.elapsed());
+        }
+
+        report(name, latencies);
+    }
+}
+
+/// Serve `{}` to every request over tls on a random port, returning the port.
+async fn serve(cert: &rcgen::Certificate, ca: &rcgen::Certificate) -> u16 {
+    let mut config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![rustls::Certificate(cert.serialize_der_with_signer(ca).unwrap())],
+            rustls::PrivateKey(cert.serialize_private_key_der()),
+        )
+        .unwrap();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(stream) = acceptor.accept(stream).await else { return };
+                let service = service_fn(|_: Request<Body>| async {
+                    Ok::<_, Infallible>(Response::new(Body::from("{}")))
+                });
+                let _ = Http::new().serve_connection(stream, service).await;
+            });
+        }
+    });
+
+    port
+}
+
+fn report(name: &str, mut latencies: Vec<Duration>) {
+    latencies.sort();
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    let percentile = |p: usize| latencies[(latencies.len() * p / 100).min(latencies.len() - 1)];
+
+    println!(
+        "{:<20} mean {:>10.2?}  p50 {:>10.2?}  p99 {:>10.2?}",
+        name,
+        mean,
+        percentile(50),
+        percentile(99)
+    );
+}
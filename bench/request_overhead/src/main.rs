@@ -0,0 +1,124 @@
+//! Compares the allocations and latency of requests sent by the hyper transport
+//! from a buffered body, as the client does, with copying the request and its
+//! body into a new `http_types::Request` for every attempt.
+//!
+//! Run with `cargo run --release -p request_overhead`, which prints something like:
+//!
+//! ```text
+//! copying      allocs    148.0 (  505.53 KiB)  mean   120.13µs  p50   114.24µs  p99   198.42µs
+//! buffered     allocs    124.0 (  345.06 KiB)  mean    83.63µs  p50    77.30µs  p99   124.88µs
+//! ```
+//!
+//! Allocations are counted across the whole process, so they include those of
+//! the local server, which are the same for both transports.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use stripe::{Client, HyperTransport, Method, Transport, TransportFuture};
+
+const REQUESTS: usize = 500;
+
+/// The size of the form sent with each request, similar to a request with a lot of metadata.
+const BODY_SIZE: usize = 32 * 1024;
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// A transport which only implements `send`, so that every attempt copies the
+/// request and its body, as the client did before sending buffered bodies.
+struct Copying(HyperTransport);
+
+impl Transport for Copying {
+    fn send(&self, request: http_types::Request) -> TransportFuture<'_> {
+        self.0.send(request)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let url = format!("http://{}/", serve());
+    let params = serde_json::json!({ "description": "x".repeat(BODY_SIZE) });
+    let pooled = || HyperTransport::builder().pool_max_idle_per_host(8).build();
+
+    let clients = [
+        (
+            "copying",
+            Client::from_url(url.as_str(), "sk_test_123").with_transport(Copying(pooled())),
+        ),
+        ("buffered", Client::from_url(url.as_str(), "sk_test_123").with_transport(pooled())),
+    ];
+
+    for (name, client) in clients {
+        // warm up the connection pool
+        client.request_raw(Method::Post, "customers", &params).await.unwrap();
+
+        let (allocations, allocated) = (count(&ALLOCATIONS), count(&ALLOCATED));
+        let mut latencies = Vec::with_capacity(REQUESTS);
+        for _ in 0..REQUESTS {
+            let start = Instant::now();
+            client.request_raw(Method::Post, "customers", &params).await.unwrap();
+            latencies.push(start.elapsed());
+        }
+
+        let allocations = (count(&ALLOCATIONS) - allocations) as f64 / REQUESTS as f64;
+        let allocated = (count(&ALLOCATED) - allocated) as f64 / REQUESTS as f64 / 1024.0;
+        report(name, allocations, allocated, latencies);
+    }
+}
+
+fn count(counter: &AtomicUsize) -> usize {
+    counter.load(Ordering::Relaxed)
+}
+
+/// Serve `{}` to every request on a random port, returning its address.
+fn serve() -> SocketAddr {
+    let make_service = make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(|request: Request<Body>| async move {
+            hyper::body::to_bytes(request.into_body()).await?;
+            Ok::<_, hyper::Error>(Response::new(Body::from("{}")))
+        }))
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+    addr
+}
+
+fn report(name: &str, allocations: f64, allocated: f64, mut latencies: Vec<Duration>) {
+    latencies.sort();
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    let percentile = |p: usize| latencies[(latencies.len() * p / 100).min(latencies.len() - 1)];
+
+    println!(
+        "{:<12} allocs {:>8.1} ({:>8.2} KiB)  mean {:>10.2?}  p50 {:>10.2?}  p99 {:>10.2?}",
+        name,
+        allocations,
+        allocated,
+        mean,
+        percentile(50),
+        percentile(99)
+    );
+}
@@ -1,30 +1,32 @@
-use std::future::{self, Future};
-use std::pin::Pin;
+use std::sync::Arc;
 
-use async_std::task::sleep;
-use http_types::{Request, StatusCode};
+use http_types::Request;
 use serde::de::DeserializeOwned;
 
-use crate::client::request_strategy::{Outcome, RequestStrategy};
-use crate::error::{ErrorResponse, StripeError};
+#[cfg(feature = "compression")]
+use crate::client::compression;
+use crate::client::request_strategy::RequestStrategy;
+use crate::client::response_future::ResponseFuture;
+use crate::client::transport::{self, RequestConfig, Transport, TransportFuture};
+use crate::error::StripeError;
 
-pub type Response<T> = Pin<Box<dyn Future<Output = Result<T, StripeError>> + Send>>;
+pub type Response<T> = ResponseFuture<T>;
 
 #[allow(dead_code)]
 #[inline(always)]
 pub(crate) fn ok<T: Send + 'static>(ok: T) -> Response<T> {
-    Box::pin(future::ready(Ok(ok)))
+    ResponseFuture::ready(Ok(ok))
 }
 
 #[allow(dead_code)]
 #[inline(always)]
 pub(crate) fn err<T: Send + 'static>(err: StripeError) -> Response<T> {
-    Box::pin(future::ready(Err(err)))
+    ResponseFuture::ready(Err(err))
 }
 
 #[derive(Clone)]
 pub struct AsyncStdClient {
-    client: surf::Client,
+    transport: Arc<dyn Transport>,
 }
 
 impl Default for AsyncStdClient {
@@ -34,9 +36,14 @@ impl Default for AsyncStdClient {
 }
 
 impl AsyncStdClient {
-    /// Creates a new client pointed to `https://api.stripe.com/`
+    /// Creates a new client using the default [`SurfTransport`].
     pub fn new() -> Self {
-        Self { client: surf::Client::new() }
+        Self::with_transport(SurfTransport::new())
+    }
+
+    /// Creates a new client which sends its requests over the given transport.
+    pub fn with_transport(transport: impl Transport + 'static) -> Self {
+        Self { transport: Arc::new(transport) }
     }
 
     pub fn execute<T: DeserializeOwned + Send + 'static>(
@@ -44,86 +51,84 @@ impl AsyncStdClient {
         request: Request,
         strategy: &RequestStrategy,
     ) -> Response<T> {
-        // need to clone here since client could be used across threads.
-        // N.B. Client is send sync; cloned clients share the same pool.
-        let client = self.client.clone();
-        let strategy = strategy.clone();
+        self.execute_with_config(request, strategy.into())
+    }
 
-        Box::pin(async move {
-            let bytes = send_inner(&client, request, &strategy).await?;
-            let json_deserializer = &mut serde_json::Deserializer::from_slice(&bytes);
-            serde_path_to_error::deserialize(json_deserializer).map_err(StripeError::from)
+    pub(crate) fn execute_with_config<T: DeserializeOwned + Send + 'static>(
+        &self,
+        request: Request,
+        config: RequestConfig,
+    ) -> Response<T> {
+        self.execute_map(request, config, |value| value)
+    }
+
+    /// Send the request and map its result, within the one boxed future of the request.
+    pub(crate) fn execute_map<T, U>(
+        &self,
+        request: Request,
+        config: RequestConfig,
+        f: impl FnOnce(T) -> U + Send + 'static,
+    ) -> Response<U>
+    where
+        T: DeserializeOwned + Send + 'static,
+        U: Send + 'static,
+    {
+        // need to clone here since client could be used across threads.
+        // N.B. Client is send sync; cloned clients share the same transport.
+        let transport = self.transport.clone();
+        ResponseFuture::new(async move {
+            transport::execute(&*transport, request, &config).await.map(f)
         })
     }
 }
 
-async fn send_inner(
-    client: &surf::Client,
-    mut request: Request,
-    strategy: &RequestStrategy,
-) -> Result<Vec<u8>, StripeError> {
-    let mut tries = 0;
-    let mut last_status: Option<StatusCode> = None;
-    let mut last_retry_header: Option<bool> = None;
-
-    // if we have no last error, then the strategy is invalid
-    let mut last_error = StripeError::ClientError("Invalid strategy".to_string());
+/// The default [`Transport`] for the async-std runtime, backed by a `surf` client.
+#[derive(Clone)]
+pub struct SurfTransport {
+    client: surf::Client,
+}
 
-    if let Some(key) = strategy.get_key() {
-        request.insert_header("Idempotency-Key", key);
+impl Default for SurfTransport {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    let body = request.body_bytes().await?;
+impl SurfTransport {
+    pub fn new() -> Self {
+        Self { client: surf::Client::new() }
+    }
+}
 
-    loop {
-        return match strategy.test(last_status, last_retry_header, tries) {
-            Outcome::Stop => Err(last_error),
-            Outcome::Continue(duration) => {
-                if let Some(duration) = duration {
-                    sleep(duration).await;
-                }
+impl Transport for SurfTransport {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        #[cfg(feature = "compression")]
+        let mut request = request;
+        #[cfg(feature = "compression")]
+        if request.header("accept-encoding").is_none() {
+            request.insert_header("accept-encoding", compression::ACCEPT_ENCODING);
+        }
 
-                // we need to clone the request before sending it so we can
-                // re-use it if we need to retry. ditto for the body
-                let mut request = request.clone();
-                request.set_body(body.clone());
-
-                let mut response = match client.send(request).await {
-                    Ok(response) => response,
-                    Err(err) => {
-                        last_error = StripeError::from(err);
-                        tries += 1;
-                        continue;
-                    }
-                };
-
-                let status = response.status();
-                let retry = response
-                    .header("Stripe-Should-Retry")
-                    .and_then(|s| s.last().as_str().parse().ok());
-
-                // if this fails parsing, we can probably just exit
-                let bytes = response.body_bytes().await?;
-
-                if !status.is_success() {
-                    tries += 1;
-                    let json_deserializer = &mut serde_json::Deserializer::from_slice(&bytes);
-                    last_error = serde_path_to_error::deserialize(json_deserializer)
-                        .map(|mut e: ErrorResponse| {
-                            e.error.http_status = status.into();
-                            StripeError::from(e.error)
-                        })
-                        .unwrap_or_else(StripeError::from);
-                    last_status = Some(status);
-                    last_retry_header = retry;
-
-                    continue;
-                }
+        Box::pin(async move {
+            let response: http_types::Response = self.client.send(request).await?.into();
+            #[cfg(feature = "compression")]
+            let response = decompress(response).await?;
+            Ok(response)
+        })
+    }
+}
 
-                Ok(bytes)
-            }
-        };
+/// Decompress the body of a response, unless the http client has already done so.
+#[cfg(feature = "compression")]
+async fn decompress(
+    mut response: http_types::Response,
+) -> Result<http_types::Response, StripeError> {
+    if let Some(encoding) = response.remove_header("content-encoding") {
+        response.remove_header("content-length");
+        let body = response.body_bytes().await?;
+        response.set_body(compression::decompress(Some(encoding.last().as_str()), body)?);
     }
+    Ok(response)
 }
 
 #[cfg(test)]
@@ -1,13 +1,17 @@
-use std::{sync::Arc, time::Duration};
+use std::future::Future;
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
 
 use http_types::Request;
 use serde::de::DeserializeOwned;
 
 use crate::client::base::tokio::TokioClient;
 use crate::client::request_strategy::RequestStrategy;
+use crate::client::transport::{RequestConfig, Transport};
 use crate::error::StripeError;
 
-/// The delay after which the blocking `Client` will assume the request has failed.
+/// The delay after which the blocking `Client` will assume the request has failed,
+/// including any retries, unless a timeout has been configured on the client.
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub type Response<T> = Result<T, StripeError>;
@@ -22,10 +26,28 @@ pub(crate) fn err<T>(err: crate::StripeError) -> Response<T> {
     Err(err)
 }
 
+/// The runtime shared by all blocking clients, created on first use.
+///
+/// Requests are spawned onto its worker thread rather than run on the calling
+/// thread, so that the blocking client may be called from within another tokio
+/// runtime, where `block_on` would panic.
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("stripe-blocking")
+            .enable_io()
+            .enable_time() // use separate `io/time` instead of `all` to ensure `tokio/time` is enabled
+            .build()
+            .expect("should be able to get a runtime")
+    })
+}
+
 #[derive(Clone)]
 pub struct TokioBlockingClient {
     inner: TokioClient,
-    runtime: Arc<tokio::runtime::Runtime>,
+    default_timeout: Duration,
 }
 
 impl Default for TokioBlockingClient {
@@ -40,13 +62,13 @@ impl TokioBlockingClient {
         TokioBlockingClient::from_async(TokioClient::new())
     }
 
+    /// Creates a new client which sends its requests over the given transport.
+    pub fn with_transport(transport: impl Transport + 'static) -> TokioBlockingClient {
+        TokioBlockingClient::from_async(TokioClient::with_transport(transport))
+    }
+
     fn from_async(inner: TokioClient) -> TokioBlockingClient {
-        let runtime = tokio::runtime::Builder::new_current_thread()
-            .enable_io()
-            .enable_time() // use separate `io/time` instead of `all` to ensure `tokio/time` is enabled
-            .build()
-            .expect("should be able to get a runtime");
-        TokioBlockingClient { inner, runtime: Arc::new(runtime) }
+        TokioBlockingClient { inner, default_timeout: DEFAULT_TIMEOUT }
     }
 
     pub fn execute<T: DeserializeOwned + Send + 'static>(
@@ -54,14 +76,132 @@ impl TokioBlockingClient {
         request: Request,
         strategy: &RequestStrategy,
     ) -> Response<T> {
-        let future = self.inner.execute(request, strategy);
-        match self.runtime.block_on(async {
-            // N.B. The `tokio::time::timeout` must be called from within a running async
-            //      context or else it will panic (it registers with the thread-local timer).
-            tokio::time::timeout(DEFAULT_TIMEOUT, future).await
-        }) {
-            Ok(finished) => finished,
-            Err(_) => Err(StripeError::Timeout),
+        self.execute_with_config(request, strategy.into())
+    }
+
+    pub(crate) fn execute_with_config<T: DeserializeOwned>(
+        &self,
+        request: Request,
+        mut config: RequestConfig,
+    ) -> Response<T> {
+        // N.B. Without any timeout configured, the whole call, retries included, is
+        //      bounded by the default. Timeouts are applied by the async client from
+        //      within the runtime, as they must be called from a running async
+        //      context or else they will panic (they register with the thread-local
+        //      timer).
+        if config.timeout.is_none() && config.total_timeout.is_none() {
+            config.total_timeout = Some(self.default_timeout);
+        }
+
+        // N.B. Only the raw body is sent back, so that the response is deserialized
+        //      on the calling thread instead of holding up the single worker.
+        let inner = self.inner.clone();
+        let task_config = config.clone();
+        let body = self.block_on(async move { inner.fetch(request, &task_config).await });
+        config.decode(body)
+    }
+
+    /// Run a future on the shared runtime, blocking the calling thread until it completes.
+    pub(crate) fn block_on<T: Send + 'static>(
+        &self,
+        future: impl Future<Output = Response<T>> + Send + 'static,
+    ) -> Response<T> {
+        // N.B. The calling thread waits on a channel instead of entering the runtime,
+        //      which is safe whether or not it is itself driving an async runtime.
+        let (tx, rx) = mpsc::sync_channel(1);
+        runtime().spawn(async move {
+            let _ = tx.send(future.await);
+        });
+        rx.recv().unwrap_or_else(|_| {
+            Err(StripeError::ClientError("the request was dropped by the runtime".to_string()))
+        })
+    }
+
+    pub(crate) fn execute_map<T: DeserializeOwned, U>(
+        &self,
+        request: Request,
+        config: RequestConfig,
+        f: impl FnOnce(T) -> U,
+    ) -> Response<U> {
+        self.execute_with_config(request, config).map(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http_types::{Request, Url};
+
+    use super::TokioBlockingClient;
+    use crate::client::request_strategy::RequestStrategy;
+    use crate::client::transport::{Transport, TransportFuture};
+    use crate::StripeError;
+
+    struct FakeTransport;
+
+    impl Transport for FakeTransport {
+        fn send(&self, _request: Request) -> TransportFuture<'_> {
+            Box::pin(async {
+                let mut response = http_types::Response::new(200);
+                response.set_body("{\"id\": \"test\"}");
+                Ok(response)
+            })
+        }
+    }
+
+    #[test]
+    fn outside_runtime() {
+        let client = TokioBlockingClient::with_transport(FakeTransport);
+        let req = Request::get(Url::parse("https://api.stripe.com/v1/balance").unwrap());
+        let res = client.execute::<serde_json::Value>(req, &RequestStrategy::Once);
+        assert_eq!(res.unwrap()["id"], "test");
+    }
+
+    #[tokio::test]
+    async fn inside_runtime() {
+        let client = TokioBlockingClient::with_transport(FakeTransport);
+        let req = Request::get(Url::parse("https://api.stripe.com/v1/balance").unwrap());
+        let res = client.execute::<serde_json::Value>(req, &RequestStrategy::Once);
+        assert_eq!(res.unwrap()["id"], "test");
+    }
+
+    struct ThreadName(Option<String>);
+
+    impl<'de> serde::Deserialize<'de> for ThreadName {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            serde::de::IgnoredAny::deserialize(deserializer)?;
+            Ok(ThreadName(std::thread::current().name().map(str::to_string)))
+        }
+    }
+
+    #[test]
+    fn decodes_on_calling_thread() {
+        let client = TokioBlockingClient::with_transport(FakeTransport);
+        let req = Request::get(Url::parse("https://api.stripe.com/v1/balance").unwrap());
+        let res = client.execute::<ThreadName>(req, &RequestStrategy::Once).unwrap();
+        assert_ne!(res.0.as_deref(), Some("stripe-blocking"));
+    }
+
+    struct SlowFailingTransport;
+
+    impl Transport for SlowFailingTransport {
+        fn send(&self, _request: Request) -> TransportFuture<'_> {
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let mut response = http_types::Response::new(500);
+                response.set_body("{\"error\": {\"type\": \"api_error\"}}");
+                Ok(response)
+            })
         }
     }
+
+    #[test]
+    fn default_timeout_covers_retries() {
+        let mut client = TokioBlockingClient::with_transport(SlowFailingTransport);
+        client.default_timeout = Duration::from_millis(100);
+        let req = Request::get(Url::parse("https://api.stripe.com/v1/balance").unwrap());
+        let res = client.execute::<serde_json::Value>(req, &RequestStrategy::Retry(1000));
+        assert!(matches!(res, Err(StripeError::Timeout)));
+    }
 }
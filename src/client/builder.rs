@@ -0,0 +1,362 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_types::Url;
+
+use crate::{
+    client::{
+        mode::Mode,
+        request_strategy::RequestStrategy,
+        secret::{CachedSecret, SecretKey, SecretProvider},
+        Transport,
+    },
+    AccountId, ApiVersion, ApplicationId, Client, StripeError,
+};
+
+/// A builder for a [`Client`], which validates its configuration instead of panicking.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use stripe::{ClientBuilder, RequestStrategy, StripeError};
+/// # fn run() -> Result<(), StripeError> {
+/// let client = ClientBuilder::from_env()?
+///     .strategy(RequestStrategy::ExponentialBackoff(3))
+///     .timeout(Duration::from_secs(10))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct ClientBuilder {
+    secret_key: SecretKey,
+    secret_provider: Option<Arc<CachedSecret>>,
+    api_base: Option<String>,
+    stripe_version: Option<ApiVersion>,
+    app_info: Option<(String, Option<String>, Option<String>)>,
+    stripe_account: Option<AccountId>,
+    client_id: Option<ApplicationId>,
+    strategy: Option<RequestStrategy>,
+    timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    transport: Option<Arc<dyn Transport>>,
+    expected_mode: Option<Mode>,
+}
+
+impl ClientBuilder {
+    /// Create a builder for a client using the given secret key.
+    pub fn new(secret_key: impl Into<SecretKey>) -> Self {
+        Self { secret_key: secret_key.into(), ..Default::default() }
+    }
+
+    /// Create a builder configured from the environment:
+    ///
+    /// - `STRIPE_SECRET_KEY`: the secret key, which is required
+    /// - `STRIPE_API_BASE`: the url of the api, if not `https://api.stripe.com/`
+    /// - `STRIPE_ACCOUNT`: the connected account to make requests on behalf of
+    pub fn from_env() -> Result<Self, StripeError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, StripeError> {
+        let secret_key = var("STRIPE_SECRET_KEY")
+            .ok_or_else(|| StripeError::Config("STRIPE_SECRET_KEY is not set".to_string()))?;
+
+        let mut builder = Self::new(secret_key);
+        builder.api_base = var("STRIPE_API_BASE");
+        if let Some(account) = var("STRIPE_ACCOUNT") {
+            let account = account.parse().map_err(|_| {
+                StripeError::Config(format!("STRIPE_ACCOUNT is not an account id: {}", account))
+            })?;
+            builder.stripe_account = Some(account);
+        }
+        Ok(builder)
+    }
+
+    /// Get the secret key for each request from the given provider, instead of
+    /// using a fixed key, which must then be left empty:
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use stripe::{ClientBuilder, Mode, SecretProvider, StripeError};
+    /// # fn run(vault: impl SecretProvider + 'static) -> Result<(), StripeError> {
+    /// let client = ClientBuilder::default()
+    ///     .secret_provider(vault, Duration::from_secs(300))
+    ///     .expected_mode(Mode::Live)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// As the key is not known until it is fetched, an expected mode is checked
+    /// against each key the provider returns, rather than when building the client.
+    /// See [`Client::with_secret_provider`].
+    pub fn secret_provider(
+        mut self,
+        provider: impl SecretProvider + 'static,
+        max_age: Duration,
+    ) -> Self {
+        self.secret_provider = Some(Arc::new(CachedSecret::new(provider, max_age)));
+        self
+    }
+
+    /// Set the url of the api, for example to point the client at a mock server.
+    pub fn api_base(mut self, url: impl Into<String>) -> Self {
+        self.api_base = Some(url.into());
+        self
+    }
+
+    /// Set the version of the api to use.
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.stripe_version = Some(version);
+        self
+    }
+
+    /// Set the application info for the client.
+    ///
+    /// See [`Client::with_app_info`].
+    pub fn app_info(
+        mut self,
+        name: impl Into<String>,
+        version: Option<String>,
+        url: Option<String>,
+    ) -> Self {
+        self.app_info = Some((name.into(), version, url));
+        self
+    }
+
+    /// Make requests on behalf of the given connected account.
+    pub fn stripe_account(mut self, id: AccountId) -> Self {
+        self.stripe_account = Some(id);
+        self
+    }
+
+    /// Set the client id for the client.
+    pub fn client_id(mut self, id: ApplicationId) -> Self {
+        self.client_id = Some(id);
+        self
+    }
+
+    /// Set the request strategy for the client.
+    pub fn strategy(mut self, strategy: RequestStrategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Set the maximum time a single attempt at a request may take.
+    ///
+    /// See [`Client::with_timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum time a request may take, including all of its retries.
+    ///
+    /// See [`Client::with_total_timeout`].
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum time to wait for a connection to stripe to be established.
+    ///
+    /// This configures the default transport, so it cannot be combined with
+    /// [`transport`](Self::transport). It is only supported by the hyper transport
+    /// of the tokio runtimes, and building the client fails on other runtimes.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Send all requests over the given transport.
+    ///
+    /// See [`Client::with_transport`].
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Fail to build the client unless its key is for the given mode.
+    ///
+    /// The client also keeps checking the mode, see [`Client::with_expected_mode`].
+    pub fn expected_mode(mut self, mode: Mode) -> Self {
+        self.expected_mode = Some(mode);
+        self
+    }
+
+    /// Build the client, failing if the configuration is invalid.
+    pub fn build(self) -> Result<Client, StripeError> {
+        if self.secret_provider.is_some() {
+            if !self.secret_key.expose().is_empty() {
+                return Err(StripeError::Config(
+                    "a secret key cannot be combined with a secret provider".to_string(),
+                ));
+            }
+        } else {
+            self.check_key()?;
+        }
+
+        let api_base = self.api_base.as_deref().unwrap_or("https://api.stripe.com/");
+        Url::parse(api_base)
+            .map_err(|e| StripeError::Config(format!("invalid api base {}: {}", api_base, e)))?;
+
+        let mut client = Client::from_url(api_base, self.secret_key);
+        if let Some(secret) = self.secret_provider {
+            client = client.with_cached_secret(secret);
+        }
+
+        match (self.transport, self.connect_timeout) {
+            (Some(_), Some(_)) => {
+                return Err(StripeError::Config(
+                    "a connect timeout cannot be combined with a custom transport".to_string(),
+                ))
+            }
+            (Some(transport), None) => client = client.with_transport(transport),
+            #[cfg(feature = "hyper-client")]
+            (None, Some(timeout)) => {
+                client = client.with_transport(
+                    crate::HyperTransport::builder().connect_timeout(timeout).try_build()?,
+                )
+            }
+            #[cfg(not(feature = "hyper-client"))]
+            (None, Some(_)) => {
+                return Err(StripeError::Config(
+                    "a connect timeout is only supported by the hyper transport".to_string(),
+                ))
+            }
+            _ => {}
+        }
+
+        if let Some((name, version, url)) = self.app_info {
+            client = client.with_app_info(name, version, url);
+        }
+        if let Some(id) = self.client_id {
+            client = client.with_client_id(id);
+        }
+        if let Some(strategy) = self.strategy {
+            client = client.with_strategy(strategy);
+        }
+        if let Some(timeout) = self.timeout {
+            client = client.with_timeout(timeout);
+        }
+        if let Some(timeout) = self.total_timeout {
+            client = client.with_total_timeout(timeout);
+        }
+        if let Some(mode) = self.expected_mode {
+            client = client.with_expected_mode(mode);
+        }
+
+        if let Some(id) = self.stripe_account {
+            client = client.with_stripe_account(id);
+        }
+        if let Some(version) = self.stripe_version {
+            client = client.with_stripe_version(version);
+        }
+
+        Ok(client)
+    }
+
+    fn check_key(&self) -> Result<(), StripeError> {
+        if self.secret_key.expose().is_empty() {
+            return Err(StripeError::Config("the secret key is empty".to_string()));
+        }
+        if self.secret_key.expose().starts_with("pk_") {
+            return Err(StripeError::Config(
+                "a publishable key (pk_) cannot be used to make api requests, \
+                 use a secret (sk_) or restricted (rk_) key instead"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(mode) = self.expected_mode {
+            mode.check_key(self.secret_key.expose())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::ClientBuilder;
+    use crate::{
+        ApiVersion, Method, Mode, RequestOptions, SecretFuture, SecretKey, SecretProvider,
+        StripeError,
+    };
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<ClientBuilder, StripeError> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        ClientBuilder::from_vars(|name| vars.get(name).map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn from_env() {
+        let builder = from_vars(&[
+            ("STRIPE_SECRET_KEY", "sk_test_123"),
+            ("STRIPE_API_BASE", "http://localhost:12111/"),
+            ("STRIPE_ACCOUNT", "acct_123"),
+        ])
+        .unwrap();
+        assert_eq!(builder.secret_key.expose(), "sk_test_123");
+        assert_eq!(builder.api_base.as_deref(), Some("http://localhost:12111/"));
+        assert_eq!(builder.stripe_account.as_deref(), Some("acct_123"));
+        assert!(builder.build().is_ok());
+
+        assert!(matches!(from_vars(&[]), Err(StripeError::Config(_))));
+        assert!(matches!(
+            from_vars(&[("STRIPE_SECRET_KEY", "sk_test_123"), ("STRIPE_ACCOUNT", "cus_123")]),
+            Err(StripeError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn api_version() {
+        let client = ClientBuilder::new("sk_test_123").api_version(ApiVersion::V2022_11_15).build();
+        let client = client.unwrap().with_options(RequestOptions {
+            stripe_account: Some("acct_123".parse().unwrap()),
+            ..Default::default()
+        });
+        let request = client.raw_request(Method::Get, "balance", serde_json::json!({})).unwrap();
+        assert_eq!(request.header("stripe-version").unwrap(), ApiVersion::V2022_11_15.as_str());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let build = |builder: ClientBuilder| matches!(builder.build(), Err(StripeError::Config(_)));
+
+        assert!(build(ClientBuilder::new("")));
+        assert!(build(ClientBuilder::new("pk_test_123")));
+        assert!(build(ClientBuilder::new("sk_test_123").api_base("not a url")));
+        assert!(!build(ClientBuilder::new("rk_test_123")));
+        assert!(build(ClientBuilder::new("sk_live_123").expected_mode(Mode::Test)));
+        assert!(!build(ClientBuilder::new("sk_live_123").expected_mode(Mode::Live)));
+
+        // a provider replaces the key, which is only known once it is fetched
+        struct Vault;
+        impl SecretProvider for Vault {
+            fn fetch(&self) -> SecretFuture<'_> {
+                Box::pin(async { Ok(SecretKey::new("sk_test_123")) })
+            }
+        }
+        let provider = || ClientBuilder::default().secret_provider(Vault, Duration::from_secs(60));
+        assert!(!build(provider()));
+        assert!(!build(provider().expected_mode(Mode::Live)));
+        assert!(build(ClientBuilder::new("sk_test_123").secret_provider(Vault, Duration::ZERO)));
+
+        let timeout = std::time::Duration::from_secs(5);
+        #[cfg(feature = "hyper-client")]
+        {
+            assert!(!build(ClientBuilder::new("sk_test_123").connect_timeout(timeout)));
+            assert!(build(
+                ClientBuilder::new("sk_test_123")
+                    .connect_timeout(timeout)
+                    .transport(crate::HyperTransport::builder().build())
+            ));
+        }
+        #[cfg(not(feature = "hyper-client"))]
+        assert!(build(ClientBuilder::new("sk_test_123").connect_timeout(timeout)));
+    }
+}
@@ -0,0 +1,95 @@
+//! Compression of response bodies, enabled by the `compression` feature.
+
+use std::io::Read;
+
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+
+use crate::StripeError;
+
+/// The value of the `Accept-Encoding` header sent with every request.
+pub(crate) const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+/// Decompress a response body according to its `Content-Encoding` header.
+///
+/// Some http clients already decompress the bodies they receive without removing
+/// the header, so a `gzip` body without the gzip magic number is returned as is.
+/// A `deflate` body is decoded as zlib if it starts with a zlib header, returned
+/// as is if it already looks like json, and decoded as a raw deflate stream
+/// otherwise.
+pub(crate) fn decompress(encoding: Option<&str>, body: Vec<u8>) -> Result<Vec<u8>, StripeError> {
+    if body.is_empty() {
+        return Ok(body);
+    }
+
+    let decoded = match encoding.map(|e| e.trim().to_ascii_lowercase()).as_deref() {
+        Some("gzip" | "x-gzip") if is_gzip(&body) => read(GzDecoder::new(body.as_slice())),
+        // `deflate` is meant to be zlib, but some servers send a raw deflate stream
+        Some("deflate") if is_zlib(&body) => read(ZlibDecoder::new(body.as_slice())),
+        Some("deflate") if is_json(&body) => return Ok(body),
+        Some("deflate") => read(DeflateDecoder::new(body.as_slice())),
+        _ => return Ok(body),
+    };
+    decoded.map_err(|e| StripeError::ClientError(format!("invalid compressed body: {}", e)))
+}
+
+/// Whether a body starts with the gzip magic number, see RFC 1952.
+fn is_gzip(body: &[u8]) -> bool {
+    body.starts_with(&[0x1f, 0x8b])
+}
+
+/// Whether a body starts with a zlib header, see RFC 1950.
+fn is_zlib(body: &[u8]) -> bool {
+    match body {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// Whether a body starts like a json object or array, after any whitespace.
+fn is_json(body: &[u8]) -> bool {
+    matches!(body.iter().find(|b| !b.is_ascii_whitespace()), Some(b'{' | b'['))
+}
+
+fn read(mut decoder: impl Read) -> std::io::Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    decoder.read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+
+    use super::decompress;
+
+    const BODY: &[u8] = br#"{"object": "list", "data": []}"#;
+
+    #[test]
+    fn encodings() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(BODY).unwrap();
+        assert_eq!(decompress(Some("gzip"), gzip.finish().unwrap()).unwrap(), BODY);
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(BODY).unwrap();
+        assert_eq!(decompress(Some("deflate"), zlib.finish().unwrap()).unwrap(), BODY);
+
+        let mut deflate = DeflateEncoder::new(Vec::new(), Compression::default());
+        deflate.write_all(BODY).unwrap();
+        assert_eq!(decompress(Some("Deflate"), deflate.finish().unwrap()).unwrap(), BODY);
+
+        // bodies which are not compressed are passed through
+        assert_eq!(decompress(None, BODY.to_vec()).unwrap(), BODY);
+        assert_eq!(decompress(Some("gzip"), BODY.to_vec()).unwrap(), BODY);
+        assert_eq!(decompress(Some("deflate"), BODY.to_vec()).unwrap(), BODY);
+        assert_eq!(decompress(Some("deflate"), b"\n [1]".to_vec()).unwrap(), b"\n [1]");
+        assert_eq!(decompress(Some("br"), BODY.to_vec()).unwrap(), BODY);
+
+        // other bodies are decoded as the header says, and fail if they are not compressed
+        assert!(decompress(Some("gzip"), vec![0x1f, 0x8b, 0]).is_err());
+        assert!(decompress(Some("deflate"), b"not json".to_vec()).is_err());
+    }
+}
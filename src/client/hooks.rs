@@ -0,0 +1,54 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_types::Request;
+
+use crate::client::response_meta::ResponseMeta;
+use crate::StripeError;
+
+type BeforeRequest = Arc<dyn Fn(&mut Request) + Send + Sync>;
+type AfterResponse = Arc<dyn Fn(&ResponseMeta, Duration) + Send + Sync>;
+type OnError = Arc<dyn Fn(&StripeError) + Send + Sync>;
+
+/// The hooks registered on a [`Client`](crate::Client), which are called by
+/// the request loop in [`transport`](super::transport) whatever the runtime.
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    pub before_request: Vec<BeforeRequest>,
+    pub after_response: Vec<AfterResponse>,
+    pub on_error: Vec<OnError>,
+}
+
+impl Hooks {
+    /// Called once for each request, before its first attempt.
+    pub fn before_request(&self, request: &mut Request) {
+        for hook in &self.before_request {
+            hook(request);
+        }
+    }
+
+    /// Called for each response received, with the time taken by its attempt.
+    pub fn after_response(&self, meta: &ResponseMeta, latency: Duration) {
+        for hook in &self.after_response {
+            hook(meta, latency);
+        }
+    }
+
+    /// Called once for each request which fails, after any retries.
+    pub fn on_error(&self, error: &StripeError) {
+        for hook in &self.on_error {
+            hook(error);
+        }
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("before_request", &self.before_request.len())
+            .field("after_response", &self.after_response.len())
+            .field("on_error", &self.on_error.len())
+            .finish()
+    }
+}
@@ -0,0 +1,297 @@
+//! Observability hooks called by the request loop in [`transport`](super::transport).
+//!
+//! Each hook is a no-op unless the corresponding feature is enabled. Only the
+//! method, path, connected account, idempotency key and response metadata of a
+//! request are ever recorded: never its body or its authorization header.
+//!
+//! With the `metrics` feature, the following metrics are reported to the
+//! installed recorder, labelled with the `method` and `endpoint` (the path
+//! template, such as `/v1/customers/{id}`) of the request:
+//!
+//! - `stripe_requests_total`: a counter of the requests which succeeded or failed
+//!   for good, also labelled with the final `status` and `error_type`
+//! - `stripe_request_retries_total`: a counter of the retried attempts
+//! - `stripe_request_duration_seconds`: a histogram of the time taken by requests,
+//!   including their retries
+
+use std::future::Future;
+use std::time::Duration;
+
+use http_types::{Request, StatusCode};
+#[cfg(feature = "tracing")]
+use tracing::{field::Empty, Instrument};
+
+use crate::StripeError;
+
+/// Records the progress of a single request, from its first attempt until
+/// it succeeds or fails for good.
+pub(crate) struct Instrumentation {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "metrics")]
+    method: String,
+    #[cfg(feature = "metrics")]
+    endpoint: String,
+}
+
+impl Instrumentation {
+    /// Start recording a request which is about to be sent.
+    #[cfg_attr(not(any(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+    pub(crate) fn new(request: &Request) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "stripe_request",
+                method = %request.method(),
+                path = %path_template(request.url().path()),
+                account = request.header("Stripe-Account").map(|h| h.last().as_str()),
+                idempotency_key = request.header("Idempotency-Key").map(|h| h.last().as_str()),
+                attempt = Empty,
+                status = Empty,
+                request_id = Empty,
+                latency_ms = Empty,
+            ),
+            #[cfg(feature = "metrics")]
+            method: request.method().to_string(),
+            #[cfg(feature = "metrics")]
+            endpoint: path_template(request.url().path()),
+        }
+    }
+
+    /// Run the future sending the request inside its span.
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        let future = future.instrument(self.span.clone());
+        future
+    }
+
+    /// Called before each attempt at sending the request, numbered from one.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn attempt(&self, attempt: u32) {
+        #[cfg(feature = "tracing")]
+        self.span.record("attempt", attempt);
+    }
+
+    /// Called when the strategy decides to retry a failed attempt.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn retry(&self, attempt: u32, delay: Option<Duration>, error: &StripeError) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(attempt = attempt, delay = ?delay, error = %error, "retrying request");
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "stripe_request_retries_total",
+            "method" => self.method.clone(),
+            "endpoint" => self.endpoint.clone(),
+        )
+        .increment(1);
+    }
+
+    /// Called when the request is delayed to stay within the rate limits.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn rate_limited(&self, delay: Duration) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(delay = ?delay, "delaying request to stay within the rate limits");
+    }
+
+    /// Called when a response is received.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn response(&self, status: StatusCode, request_id: Option<&str>) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("status", u16::from(status));
+            self.span.record("request_id", request_id);
+        }
+    }
+
+    /// Called once the request has succeeded, or failed for good.
+    #[cfg_attr(not(any(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+    pub(crate) fn finish(&self, latency: Duration, result: Result<StatusCode, &StripeError>) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            if let Err(error) = result {
+                tracing::warn!(error = %error, "request failed");
+            }
+        }
+        #[cfg(feature = "metrics")]
+        {
+            let (status, error_type) = outcome_labels(result);
+            metrics::counter!(
+                "stripe_requests_total",
+                "method" => self.method.clone(),
+                "endpoint" => self.endpoint.clone(),
+                "status" => status,
+                "error_type" => error_type,
+            )
+            .increment(1);
+            metrics::histogram!(
+                "stripe_request_duration_seconds",
+                "method" => self.method.clone(),
+                "endpoint" => self.endpoint.clone(),
+            )
+            .record(latency.as_secs_f64());
+        }
+    }
+}
+
+/// The `status` and `error_type` labels describing how a request ended.
+///
+/// Failures which did not come from stripe have no status, and are reported as
+/// either a `timeout` or a `client_error`.
+#[cfg(feature = "metrics")]
+fn outcome_labels(result: Result<StatusCode, &StripeError>) -> (String, String) {
+    match result {
+        Ok(status) => (u16::from(status).to_string(), "none".to_string()),
+        Err(StripeError::Stripe(error)) => {
+            (error.http_status.to_string(), error.error_type.to_string())
+        }
+        Err(StripeError::Timeout) => ("none".to_string(), "timeout".to_string()),
+        Err(_) => ("none".to_string(), "client_error".to_string()),
+    }
+}
+
+/// The words which make up the paths of the api endpoints, in sorted order.
+///
+/// Any other segment of a path is an id, or some other value chosen by the
+/// caller, such as a coupon code or a price lookup key.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+const ENDPOINT_WORDS: &[&str] = &[
+    "account_links",
+    "account_sessions",
+    "accounts",
+    "advance",
+    "application_fees",
+    "approve",
+    "attach",
+    "authorizations",
+    "balance",
+    "balance_transactions",
+    "billing_portal",
+    "cancel",
+    "capture",
+    "cardholders",
+    "cards",
+    "charges",
+    "checkout",
+    "configurations",
+    "confirm",
+    "connection_tokens",
+    "country_specs",
+    "coupons",
+    "credit_notes",
+    "customer_sessions",
+    "customers",
+    "decline",
+    "detach",
+    "disputes",
+    "ephemeral_keys",
+    "events",
+    "exchange_rates",
+    "expire",
+    "file_links",
+    "files",
+    "finalize",
+    "financial_connections",
+    "invoiceitems",
+    "invoices",
+    "issuing",
+    "line_items",
+    "locations",
+    "login_links",
+    "mandates",
+    "pay",
+    "payment_intents",
+    "payment_links",
+    "payment_method_configurations",
+    "payment_method_domains",
+    "payment_methods",
+    "payouts",
+    "plans",
+    "prices",
+    "products",
+    "promotion_codes",
+    "quotes",
+    "readers",
+    "refunds",
+    "reversals",
+    "reviews",
+    "search",
+    "sessions",
+    "setup_attempts",
+    "setup_intents",
+    "shipping_rates",
+    "source_transactions",
+    "sources",
+    "submit",
+    "subscription_items",
+    "subscription_schedules",
+    "subscriptions",
+    "tax_codes",
+    "tax_ids",
+    "tax_rates",
+    "terminal",
+    "test_clocks",
+    "test_helpers",
+    "tokens",
+    "topups",
+    "transactions",
+    "transfers",
+    "upcoming",
+    "usage_records",
+    "v1",
+    "verify",
+    "verify_microdeposits",
+    "void",
+    "webhook_endpoints",
+];
+
+/// Replace the ids in a path with `{id}`, so that it identifies the endpoint
+/// rather than the object, as in `/v1/customers/{id}`.
+///
+/// Only the words of the endpoint paths are kept, so that the template cannot
+/// hold values chosen by the caller whatever their shape. The path of a request
+/// to another endpoint, made with [`Client::request`](crate::Client::request),
+/// has its unknown segments replaced as well.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+fn path_template(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment {
+            "" => segment,
+            _ if ENDPOINT_WORDS.binary_search(&segment).is_ok() => segment,
+            _ => "{id}",
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(all(test, any(feature = "tracing", feature = "metrics")))]
+mod tests {
+    use super::path_template;
+
+    #[test]
+    fn template() {
+        assert_eq!(path_template("/v1/customers"), "/v1/customers");
+        assert_eq!(path_template("/v1/customers/cus_NffrFeUfNV2Hib"), "/v1/customers/{id}");
+        assert_eq!(
+            path_template("/v1/customers/cus_123/sources/card_1MvoiELkdIwHu7ix"),
+            "/v1/customers/{id}/sources/{id}"
+        );
+        assert_eq!(
+            path_template("/v1/payment_intents/pi_3MtwBwLkdIwHu7ix28a3tqPa/confirm"),
+            "/v1/payment_intents/{id}/confirm"
+        );
+        assert_eq!(path_template("/v1/customers/search"), "/v1/customers/search");
+        assert_eq!(path_template("/v1/coupons/SUMMER25"), "/v1/coupons/{id}");
+        assert_eq!(path_template("/v1/prices/gold_monthly"), "/v1/prices/{id}");
+        assert_eq!(path_template("/v1/products/shoes"), "/v1/products/{id}");
+        assert_eq!(
+            path_template("/v1/test_helpers/test_clocks/clock_123/advance"),
+            "/v1/test_helpers/test_clocks/{id}/advance"
+        );
+    }
+
+    #[test]
+    fn endpoint_words_are_sorted() {
+        assert!(super::ENDPOINT_WORDS.windows(2).all(|w| w[0] < w[1]));
+    }
+}
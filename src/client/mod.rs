@@ -1,5 +1,23 @@
+mod builder;
+#[cfg(feature = "compression")]
+mod compression;
+mod hooks;
+mod instrument;
+mod mode;
+mod rate_limit;
+mod request_options;
 mod request_strategy;
+mod response_future;
+mod response_meta;
+mod retry_policy;
+#[cfg(feature = "sans-io")]
+mod sans_io;
+mod secret;
+#[cfg(feature = "tower")]
+mod service;
 mod stripe;
+mod tenants;
+mod transport;
 
 mod base {
     #[cfg(any(
@@ -29,6 +47,7 @@ mod base {
     feature = "runtime-blocking-rustls-webpki"
 ))]
 pub(crate) mod config {
+    pub use super::base::tokio::{HyperTransport, HyperTransportBuilder};
     pub(crate) use super::base::tokio_blocking::{err, ok};
     pub use super::base::tokio_blocking::{Response, TokioBlockingClient as BaseClient};
 }
@@ -40,15 +59,18 @@ pub(crate) mod config {
 ))]
 pub(crate) mod config {
     pub(crate) use super::base::tokio::{err, ok};
-    pub use super::base::tokio::{Response, TokioClient as BaseClient};
+    pub use super::base::tokio::{
+        HyperTransport, HyperTransportBuilder, Response, TokioClient as BaseClient,
+    };
 }
 
 #[cfg(feature = "runtime-async-std-surf")]
 pub(crate) mod config {
     pub(crate) use super::base::async_std::{err, ok};
-    pub use super::base::async_std::{AsyncStdClient as BaseClient, Response};
+    pub use super::base::async_std::{AsyncStdClient as BaseClient, Response, SurfTransport};
 }
 
+pub use builder::ClientBuilder;
 pub use config::BaseClient;
 /// An alias for `Result`.
 ///
@@ -61,9 +83,28 @@ pub use config::BaseClient;
 /// If the `async` feature is enabled, this type is defined as:
 ///
 /// ```rust,ignore
-/// type Response<T> = Box<dyn Future<Result<T, Error>>>;
+/// type Response<T> = ResponseFuture<T>;
 /// ```
+///
+/// where [`ResponseFuture`] is a future resolving to `Result<T, Error>`.
 pub use config::Response;
-pub use request_strategy::RequestStrategy;
+#[cfg(feature = "runtime-async-std-surf")]
+pub use config::SurfTransport;
+#[cfg(feature = "hyper-client")]
+pub use config::{HyperTransport, HyperTransportBuilder};
+pub use mode::Mode;
+pub use rate_limit::{RateLimiter, RateLimits};
+pub use request_options::RequestOptions;
+pub use request_strategy::{Outcome, RequestStrategy};
+pub use response_future::{BoxedResponse, ResponseFuture};
+pub use response_meta::ResponseMeta;
+pub use retry_policy::{Attempt, Deadline, JitteredBackoff, RetryPolicy, RetryableErrors};
+#[cfg(feature = "sans-io")]
+pub use sans_io::parse_response;
+pub use secret::{SecretFuture, SecretKey, SecretProvider};
+#[cfg(feature = "tower")]
+pub use service::{ParseResponse, ParseResponseLayer, ServiceTransport};
+pub use tenants::TenantRegistry;
+pub use transport::{Transport, TransportFuture};
 
 pub use self::stripe::Client;
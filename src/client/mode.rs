@@ -0,0 +1,122 @@
+use std::fmt;
+
+use crate::StripeError;
+
+/// Whether requests are made against live data or test data.
+///
+/// Declare the mode a client is expected to run in with
+/// [`Client::with_expected_mode`](crate::Client::with_expected_mode) or
+/// [`ClientBuilder::expected_mode`](crate::ClientBuilder::expected_mode), to
+/// guard against deploying it with a key for the wrong mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Live,
+    Test,
+}
+
+impl Mode {
+    /// The mode of a secret (`sk_`) or restricted (`rk_`) key, if it is one.
+    pub fn of_key(key: &str) -> Option<Mode> {
+        match key.get(..8)? {
+            "sk_live_" | "rk_live_" => Some(Mode::Live),
+            "sk_test_" | "rk_test_" => Some(Mode::Test),
+            _ => None,
+        }
+    }
+
+    /// Whether this is live mode, matching the `livemode` field of stripe objects.
+    pub fn is_live(self) -> bool {
+        self == Mode::Live
+    }
+
+    /// Fail unless the given key is for this mode.
+    pub(crate) fn check_key(self, key: &str) -> Result<(), StripeError> {
+        match Mode::of_key(key) {
+            Some(mode) if mode == self => Ok(()),
+            Some(mode) => Err(StripeError::Config(format!(
+                "a {} mode key was used by a client expecting {} mode",
+                mode, self
+            ))),
+            None => Err(StripeError::Config(format!(
+                "the mode of the key could not be checked against the expected {} mode",
+                self
+            ))),
+        }
+    }
+
+    /// Fail if a response body holds an object from the other mode anywhere,
+    /// including in lists and expanded or nested objects.
+    pub(crate) fn check_livemode(self, bytes: &[u8]) -> Result<(), StripeError> {
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(bytes) else {
+            // let the error be reported when the body is deserialized
+            return Ok(());
+        };
+
+        let mut values = vec![&value];
+        while let Some(value) = values.pop() {
+            match value {
+                serde_json::Value::Object(object) => {
+                    match object.get("livemode").and_then(|livemode| livemode.as_bool()) {
+                        Some(livemode) if livemode != self.is_live() => {
+                            return Err(StripeError::Config(format!(
+                                "received an object with livemode {} by a client expecting {} mode",
+                                livemode, self
+                            )))
+                        }
+                        _ => values.extend(object.values()),
+                    }
+                }
+                serde_json::Value::Array(items) => values.extend(items),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Mode::Live => "live",
+            Mode::Test => "test",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mode;
+
+    #[test]
+    fn keys() {
+        assert_eq!(Mode::of_key("sk_live_123"), Some(Mode::Live));
+        assert_eq!(Mode::of_key("rk_live_123"), Some(Mode::Live));
+        assert_eq!(Mode::of_key("sk_test_123"), Some(Mode::Test));
+        assert_eq!(Mode::of_key("rk_test_123"), Some(Mode::Test));
+        assert_eq!(Mode::of_key("pk_test_123"), None);
+        assert_eq!(Mode::of_key("sk_"), None);
+
+        assert!(Mode::Test.check_key("sk_test_123").is_ok());
+        assert!(Mode::Test.check_key("sk_live_123").is_err());
+        assert!(Mode::Live.check_key("").is_err());
+    }
+
+    #[test]
+    fn livemode() {
+        assert!(Mode::Test.check_livemode(br#"{"id": "cus_123", "livemode": false}"#).is_ok());
+        assert!(Mode::Live.check_livemode(br#"{"id": "cus_123", "livemode": false}"#).is_err());
+        assert!(Mode::Live.check_livemode(br#"{"id": "bal_123"}"#).is_ok());
+
+        let list = br#"{"object": "list", "data": [{"livemode": false}, {"livemode": true}]}"#;
+        assert!(Mode::Test.check_livemode(list).is_err());
+        assert!(Mode::Test.check_livemode(br#"{"object": "list", "data": []}"#).is_ok());
+
+        let expanded = br#"{"livemode": false, "latest_invoice": {"livemode": false, "payment_intent": {"livemode": true}}}"#;
+        assert!(Mode::Test.check_livemode(expanded).is_err());
+        assert!(Mode::Live.check_livemode(expanded).is_err());
+
+        let search = br#"{"object": "search_result", "results": {"items": [{"livemode": true}]}}"#;
+        assert!(Mode::Test.check_livemode(search).is_err());
+        assert!(Mode::Live.check_livemode(search).is_ok());
+    }
+}
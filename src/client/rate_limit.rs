@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use http_types::{Method, Request};
+
+use crate::StripeError;
+
+/// The number of requests per second allowed for each kind of request.
+///
+/// For more details see <https://stripe.com/docs/rate-limits>.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimits {
+    /// The limit for `GET` requests.
+    pub read: f64,
+    /// The limit for all other requests.
+    pub write: f64,
+    /// The limit for requests to the search apis, such as `/v1/customers/search`.
+    pub search: f64,
+}
+
+impl RateLimits {
+    /// The default limits stripe applies to accounts in live mode.
+    pub const LIVE: RateLimits = RateLimits { read: 100.0, write: 100.0, search: 20.0 };
+
+    /// The default limits stripe applies to accounts in test mode.
+    pub const TEST: RateLimits = RateLimits { read: 25.0, write: 25.0, search: 20.0 };
+
+    fn check(&self) -> Result<(), StripeError> {
+        for (name, rate) in [("read", self.read), ("write", self.write), ("search", self.search)] {
+            // also rejects NaN, which would make the delay before a request invalid
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(StripeError::Config(format!(
+                    "the {} rate limit must be a positive number of requests per second, not {}",
+                    name, rate
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The kind of operation a request counts towards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Operation {
+    Read,
+    Write,
+    Search,
+}
+
+/// A client-side token bucket rate limiter, which delays requests so that they
+/// stay within stripe's rate limits rather than failing with a `429`.
+///
+/// Reads, writes and searches are limited separately. The limiter is shared by
+/// all clones of the [`Client`](crate::Client) it is set on, and can also be set on
+/// several clients, so that concurrent tasks coordinate with each other.
+///
+/// ```
+/// # use stripe::{Client, RateLimiter, RateLimits};
+/// # fn run() -> Result<(), stripe::StripeError> {
+/// let client = Client::new("sk_test_123").with_rate_limiter(RateLimiter::new(RateLimits::TEST)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    limits: RateLimits,
+    per_account: bool,
+    buckets: Arc<Mutex<HashMap<BucketKey, Bucket>>>,
+}
+
+/// Buckets are keyed by the connected account, if limiting per account, and the operation.
+type BucketKey = (Option<String>, Operation);
+
+impl RateLimiter {
+    /// Create a rate limiter which applies the given limits to all requests.
+    ///
+    /// Fails with a [`StripeError::Config`] unless every limit is finite and positive.
+    pub fn new(limits: RateLimits) -> Result<Self, StripeError> {
+        limits.check()?;
+        Ok(Self { limits, per_account: false, buckets: Default::default() })
+    }
+
+    /// Create a rate limiter which applies the given limits separately to each
+    /// connected account, as set by the `Stripe-Account` header.
+    ///
+    /// Fails with a [`StripeError::Config`] unless every limit is finite and positive.
+    pub fn per_account(limits: RateLimits) -> Result<Self, StripeError> {
+        Ok(Self { per_account: true, ..Self::new(limits)? })
+    }
+
+    /// Reserve capacity for a request, returning how long to wait before sending it.
+    pub(crate) fn reserve(&self, request: &Request) -> Option<Duration> {
+        let operation = if request.url().path().ends_with("/search") {
+            Operation::Search
+        } else if request.method() == Method::Get {
+            Operation::Read
+        } else {
+            Operation::Write
+        };
+
+        let account = match self.per_account {
+            true => request.header("Stripe-Account").map(|h| h.last().as_str().to_string()),
+            false => None,
+        };
+
+        self.reserve_at(account, operation, Instant::now())
+    }
+
+    fn reserve_at(
+        &self,
+        account: Option<String>,
+        operation: Operation,
+        now: Instant,
+    ) -> Option<Duration> {
+        let rate = match operation {
+            Operation::Read => self.limits.read,
+            Operation::Write => self.limits.write,
+            Operation::Search => self.limits.search,
+        };
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket =
+            buckets.entry((account, operation)).or_insert(Bucket { tokens: rate, at: now });
+        bucket.reserve(rate, now)
+    }
+}
+
+/// A bucket holding up to a second's worth of requests, refilling continuously.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    at: Instant,
+}
+
+impl Bucket {
+    /// Take a token from the bucket. The bucket may go into debt, in which case
+    /// the caller has to wait until the token it took would have been refilled,
+    /// which makes concurrent callers queue up behind each other.
+    fn reserve(&mut self, rate: f64, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(rate) - 1.0;
+        self.at = now;
+
+        if self.tokens >= 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(-self.tokens / rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use http_types::{Request, Url};
+
+    use super::{Operation, RateLimiter, RateLimits};
+    use crate::StripeError;
+
+    const LIMITS: RateLimits = RateLimits { read: 2.0, write: 1.0, search: 1.0 };
+
+    #[test]
+    fn burst_then_wait() {
+        let limiter = RateLimiter::new(LIMITS).unwrap();
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve_at(None, Operation::Read, now), None);
+        assert_eq!(limiter.reserve_at(None, Operation::Read, now), None);
+        assert_eq!(
+            limiter.reserve_at(None, Operation::Read, now),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(limiter.reserve_at(None, Operation::Read, now), Some(Duration::from_secs(1)));
+
+        // the bucket refills over time
+        let later = now + Duration::from_secs(2);
+        assert_eq!(limiter.reserve_at(None, Operation::Read, later), None);
+    }
+
+    #[test]
+    fn separate_buckets() {
+        let limiter = RateLimiter::new(LIMITS).unwrap();
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve_at(None, Operation::Write, now), None);
+        assert!(limiter.reserve_at(None, Operation::Write, now).is_some());
+        assert_eq!(limiter.reserve_at(None, Operation::Search, now), None);
+        assert_eq!(limiter.reserve_at(Some("acct_1".into()), Operation::Write, now), None);
+    }
+
+    #[test]
+    fn shared_between_clones() {
+        let limiter = RateLimiter::new(LIMITS).unwrap();
+        let clone = limiter.clone();
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve_at(None, Operation::Write, now), None);
+        assert!(clone.reserve_at(None, Operation::Write, now).is_some());
+    }
+
+    #[test]
+    fn per_account() {
+        let limiter = RateLimiter::per_account(LIMITS).unwrap();
+        let url = Url::parse("https://api.stripe.com/v1/customers/search").unwrap();
+
+        let mut request = Request::get(url);
+        assert_eq!(limiter.reserve(&request), None);
+        assert!(limiter.reserve(&request).is_some());
+
+        request.insert_header("Stripe-Account", "acct_1");
+        assert_eq!(limiter.reserve(&request), None);
+    }
+
+    #[test]
+    fn invalid_limits() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let limits = RateLimits { search: rate, ..LIMITS };
+            assert!(matches!(RateLimiter::new(limits), Err(StripeError::Config(_))));
+            assert!(matches!(RateLimiter::per_account(limits), Err(StripeError::Config(_))));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::{client::request_strategy::RequestStrategy, AccountId, ApiVersion};
+
+/// Options which override the configuration of a [`Client`](crate::Client)
+/// for a single request.
+///
+/// Any field which is left as `None` uses the value configured on the client.
+/// Apply them to a request with [`Client::with_options`](crate::Client::with_options):
+///
+/// ```no_run
+/// # use stripe::{Client, CreateCustomer, Customer, RequestOptions};
+/// # async fn run(client: &Client) {
+/// let options = RequestOptions {
+///     stripe_account: Some("acct_123".parse().unwrap()),
+///     idempotency_key: Some("create-customer-for-order-42".to_string()),
+///     ..Default::default()
+/// };
+/// let customer = Customer::create(&client.with_options(options), CreateCustomer::new());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+    /// The connected account to make the request on behalf of, sent as the `Stripe-Account` header.
+    pub stripe_account: Option<AccountId>,
+
+    /// The key sent as the `Idempotency-Key` header, taking precedence over
+    /// any key generated by the request strategy.
+    ///
+    /// It is only sent with the first request made with these options, see
+    /// [`Client::with_options`](crate::Client::with_options).
+    pub idempotency_key: Option<String>,
+
+    /// The version of the api to use, sent as the `Stripe-Version` header.
+    pub stripe_version: Option<ApiVersion>,
+
+    /// The strategy used to retry the request.
+    pub strategy: Option<RequestStrategy>,
+
+    /// The maximum time a single attempt at the request may take.
+    pub timeout: Option<Duration>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
@@ -1,9 +1,13 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use http_types::StatusCode;
 
-#[derive(Clone, Debug)]
+use crate::client::retry_policy::{Attempt, RetryPolicy};
+
+#[derive(Clone, Debug, Default)]
 pub enum RequestStrategy {
+    #[default]
     Once,
     /// Run it once with a given idempotency key.
     Idempotent(String),
@@ -15,25 +19,47 @@ pub enum RequestStrategy {
     /// specified number of times using the same, random,
     /// idempotency key with exponential backoff, up to n times.
     ExponentialBackoff(u32),
+    /// This strategy will retry the request according to the
+    /// given policy using the same, random, idempotency key.
+    Custom(Arc<dyn RetryPolicy>),
 }
 
 impl RequestStrategy {
+    /// Create a strategy which retries requests according to a custom policy.
+    pub fn custom(policy: impl RetryPolicy + 'static) -> Self {
+        Self::Custom(Arc::new(policy))
+    }
+
     pub fn test(
         &self,
         status: Option<StatusCode>,
         stripe_should_retry: Option<bool>,
         retry_count: u32,
     ) -> Outcome {
+        self.test_attempt(&Attempt {
+            count: retry_count,
+            status,
+            stripe_should_retry,
+            ..Default::default()
+        })
+    }
+
+    /// Decide whether to make an attempt at a request, given the previous one.
+    pub fn test_attempt(&self, attempt: &Attempt) -> Outcome {
         // if stripe explicitly says not to retry then don't
-        if !stripe_should_retry.unwrap_or(true) {
+        if !attempt.stripe_should_retry.unwrap_or(true) {
             return Outcome::Stop;
         }
 
         use RequestStrategy::*;
 
-        match (self, status, retry_count) {
-            // a strategy of once or idempotent should run once
-            (Once | Idempotent(_), _, 0) => Outcome::Continue(None),
+        match (self, attempt.status, attempt.count) {
+            // a strategy of once or idempotent should run once,
+            // and every custom strategy makes at least one attempt
+            (Once | Idempotent(_) | Custom(_), _, 0) => Outcome::Continue(None),
+
+            // custom strategies decide for themselves when to retry
+            (Custom(policy), _, _) => policy.test(attempt),
 
             // requests with idempotency keys that hit client
             // errors usually cannot be solved with retries
@@ -58,16 +84,50 @@ impl RequestStrategy {
         Self::Idempotent(Uuid::new_v4().to_string())
     }
 
+    /// Send a request once, with an idempotency key derived from the given business key.
+    ///
+    /// See [`RequestStrategy::derive_key`].
+    #[cfg(feature = "uuid")]
+    pub fn idempotent_derived(business_key: &str, operation: &str) -> Self {
+        Self::Idempotent(Self::derive_key(business_key, operation))
+    }
+
+    /// Derive an idempotency key from a business key, such as an order id, and
+    /// the name of the operation being performed on it, such as `"charge"`.
+    ///
+    /// The same inputs always produce the same key, so a request which is repeated
+    /// after a crash or restart is only ever carried out once by stripe.
+    /// Use a different operation name for each request made for the same business key.
+    ///
+    /// ```
+    /// # use stripe::{RequestOptions, RequestStrategy};
+    /// let options = RequestOptions {
+    ///     idempotency_key: Some(RequestStrategy::derive_key("order_42", "charge")),
+    ///     strategy: Some(RequestStrategy::ExponentialBackoff(3)),
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[cfg(feature = "uuid")]
+    pub fn derive_key(business_key: &str, operation: &str) -> String {
+        use uuid::Uuid;
+        let namespace =
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, b"https://crates.io/crates/async-stripe");
+        let name = [operation.as_bytes(), b"\0", business_key.as_bytes()].concat();
+        Uuid::new_v5(&namespace, &name).to_string()
+    }
+
     pub fn get_key(&self) -> Option<String> {
         match self {
             RequestStrategy::Once => None,
             RequestStrategy::Idempotent(key) => Some(key.clone()),
             #[cfg(feature = "uuid")]
-            RequestStrategy::Retry(_) | RequestStrategy::ExponentialBackoff(_) => {
-                Some(uuid::Uuid::new_v4().to_string())
-            }
+            RequestStrategy::Retry(_)
+            | RequestStrategy::ExponentialBackoff(_)
+            | RequestStrategy::Custom(_) => Some(uuid::Uuid::new_v4().to_string()),
             #[cfg(not(feature = "uuid"))]
-            RequestStrategy::Retry(_) | RequestStrategy::ExponentialBackoff(_) => None,
+            RequestStrategy::Retry(_)
+            | RequestStrategy::ExponentialBackoff(_)
+            | RequestStrategy::Custom(_) => None,
         }
     }
 }
@@ -76,9 +136,12 @@ fn calculate_backoff(retry_count: u32) -> Duration {
     Duration::from_secs(2_u64.pow(retry_count))
 }
 
+/// Whether to make another attempt at a request.
 #[derive(PartialEq, Eq, Debug)]
 pub enum Outcome {
+    /// Give up, returning the last error.
     Stop,
+    /// Make another attempt, optionally after waiting for the given duration.
     Continue(Option<Duration>),
 }
 
@@ -110,6 +173,16 @@ mod tests {
         assert!(Uuid::parse_str(&strategy.get_key().unwrap()).is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn test_derived_idempotency() {
+        let key = RequestStrategy::derive_key("order_42", "charge");
+        assert_eq!(key, RequestStrategy::derive_key("order_42", "charge"));
+        assert_ne!(key, RequestStrategy::derive_key("order_42", "refund"));
+        assert_ne!(key, RequestStrategy::derive_key("order_43", "charge"));
+        assert_eq!(RequestStrategy::idempotent_derived("order_42", "charge").get_key(), Some(key));
+    }
+
     #[test]
     #[cfg(not(feature = "uuid"))]
     fn test_uuid_idempotency() {
@@ -137,6 +210,15 @@ mod tests {
         assert_eq!(strategy.test(None, None, 4), Outcome::Stop);
     }
 
+    #[test]
+    fn test_custom_strategy() {
+        let strategy = RequestStrategy::custom(crate::JitteredBackoff::new(1));
+        assert_eq!(strategy.test(None, None, 0), Outcome::Continue(None));
+        assert!(matches!(strategy.test(None, None, 1), Outcome::Continue(Some(_))));
+        assert_eq!(strategy.test(None, None, 2), Outcome::Stop);
+        assert_eq!(strategy.test(None, Some(false), 1), Outcome::Stop);
+    }
+
     #[test]
     fn test_retry_header() {
         let strategy = RequestStrategy::Retry(3);
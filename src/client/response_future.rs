@@ -0,0 +1,147 @@
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::StripeError;
+
+/// A boxed future resolving to the result of an api call, which is what api
+/// calls returned before [`ResponseFuture`].
+///
+/// Any [`ResponseFuture`] can be converted into one with [`ResponseFuture::boxed`].
+pub type BoxedResponse<T> = Pin<Box<dyn Future<Output = Result<T, StripeError>> + Send>>;
+
+/// The future returned by api calls when using an async runtime.
+///
+/// Unlike a boxed trait object, this type can be named, for example in a struct
+/// field or as the associated type of a trait of your own. It is `Unpin`, and is
+/// `Send` whenever its output is.
+///
+/// It is not an unboxed future: a request which is sent is still driven by a
+/// boxed `Send` future, shared by the whole retry loop, as the futures of the
+/// transport and of the retry loop cannot be named. Only calls which resolve
+/// without sending a request, such as an invalid request or asking for the page
+/// after the last one of a list, skip that allocation. Paginating a list maps
+/// each page within the boxed future, while [`map`](Self::map) on a pending
+/// future boxes it once more.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ResponseFuture<T> {
+    state: State<T>,
+}
+
+enum State<T> {
+    Ready(Option<Result<T, StripeError>>),
+    Pending(BoxedResponse<T>),
+}
+
+// the output is only ever moved out of its option, and never pinned
+impl<T> Unpin for ResponseFuture<T> {}
+
+impl<T> ResponseFuture<T> {
+    /// A future resolving immediately to the given result.
+    pub(crate) fn ready(result: Result<T, StripeError>) -> Self {
+        Self { state: State::Ready(Some(result)) }
+    }
+
+    /// A future driving the given one.
+    pub(crate) fn new(
+        future: impl Future<Output = Result<T, StripeError>> + Send + 'static,
+    ) -> Self {
+        Self { state: State::Pending(Box::pin(future)) }
+    }
+
+    /// Map the value of a successful call.
+    ///
+    /// A future which is still pending is wrapped in a new boxed future.
+    ///
+    /// # Panics
+    ///
+    /// If the future has already completed.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U + Send + 'static) -> ResponseFuture<U>
+    where
+        T: Send + 'static,
+    {
+        match self.state {
+            State::Ready(result) => {
+                ResponseFuture::ready(result.expect("future polled after completion").map(f))
+            }
+            State::Pending(future) => ResponseFuture::new(async move { future.await.map(f) }),
+        }
+    }
+
+    /// Box this future, as returned by api calls before [`ResponseFuture`].
+    ///
+    /// # Panics
+    ///
+    /// If the future has already completed.
+    pub fn boxed(self) -> BoxedResponse<T>
+    where
+        T: Send + 'static,
+    {
+        match self.state {
+            State::Ready(result) => {
+                Box::pin(future::ready(result.expect("future polled after completion")))
+            }
+            State::Pending(future) => future,
+        }
+    }
+}
+
+impl<T> Future for ResponseFuture<T> {
+    type Output = Result<T, StripeError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.get_mut().state {
+            State::Ready(result) => {
+                Poll::Ready(result.take().expect("future polled after completion"))
+            }
+            State::Pending(future) => future.as_mut().poll(cx),
+        }
+    }
+}
+
+impl<T: Send + 'static> From<ResponseFuture<T>> for BoxedResponse<T> {
+    fn from(future: ResponseFuture<T>) -> Self {
+        future.boxed()
+    }
+}
+
+impl<T> std::fmt::Debug for ResponseFuture<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self.state {
+            State::Ready(_) => "ready",
+            State::Pending(_) => "pending",
+        };
+        f.debug_struct("ResponseFuture").field("state", &state).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoxedResponse, ResponseFuture};
+    use crate::StripeError;
+
+    /// A trait object naming the future, without boxing it in the trait.
+    trait Fetch {
+        fn fetch(&self) -> ResponseFuture<u32>;
+    }
+
+    struct Fixed;
+
+    impl Fetch for Fixed {
+        fn fetch(&self) -> ResponseFuture<u32> {
+            ResponseFuture::ready(Ok(1))
+        }
+    }
+
+    #[tokio::test]
+    async fn response_future() {
+        let fetch: &dyn Fetch = &Fixed;
+        assert_eq!(fetch.fetch().map(|n| n + 1).await.unwrap(), 2);
+
+        let pending = ResponseFuture::new(async { Ok(3) }).map(|n: u32| n * 2);
+        assert_eq!(pending.await.unwrap(), 6);
+
+        let boxed: BoxedResponse<u32> = ResponseFuture::ready(Err(StripeError::Timeout)).into();
+        assert!(matches!(boxed.await, Err(StripeError::Timeout)));
+    }
+}
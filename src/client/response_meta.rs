@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use http_types::{headers::Headers, StatusCode};
+
+/// Metadata about the http response stripe sent back for a request.
+///
+/// See [`Client::capture_meta`](crate::Client::capture_meta) for how to get hold of it.
+#[derive(Clone, Debug)]
+pub struct ResponseMeta {
+    /// The http status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: Headers,
+}
+
+impl ResponseMeta {
+    pub(crate) fn new(response: &http_types::Response) -> Self {
+        let headers: &Headers = response.as_ref();
+        Self { status: response.status(), headers: headers.clone() }
+    }
+
+    /// Get the value of a response header, if it is present.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|values| values.last().as_str())
+    }
+
+    /// The id stripe assigned to the request, from the `Request-Id` header.
+    ///
+    /// Provide this when contacting stripe support about a request.
+    pub fn request_id(&self) -> Option<&str> {
+        self.header("Request-Id")
+    }
+
+    /// Whether stripe replayed the response of an earlier request made with the
+    /// same idempotency key, from the `Idempotent-Replayed` header.
+    pub fn idempotent_replayed(&self) -> bool {
+        self.header("Idempotent-Replayed") == Some("true")
+    }
+
+    /// Whether stripe suggests the request should be retried, from the `Stripe-Should-Retry` header.
+    pub fn should_retry(&self) -> Option<bool> {
+        self.header("Stripe-Should-Retry").and_then(|s| s.parse().ok())
+    }
+
+    /// How long stripe asks to wait before retrying the request, from the `Retry-After` header.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.header("Retry-After").and_then(|s| s.parse().ok()).map(Duration::from_secs)
+    }
+}
+
+/// A place for the client to store the metadata of the last response it received.
+pub(crate) type ResponseMetaSlot = Arc<Mutex<Option<ResponseMeta>>>;
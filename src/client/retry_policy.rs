@@ -0,0 +1,245 @@
+use std::collections::hash_map::RandomState;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use http_types::StatusCode;
+
+use crate::client::request_strategy::Outcome;
+use crate::error::{ErrorCode, RequestError, StripeError};
+
+/// The result of the last attempt at a request, used by a [`RetryPolicy`]
+/// to decide whether to try again.
+#[derive(Debug, Default)]
+pub struct Attempt<'a> {
+    /// The number of attempts which have been made so far.
+    pub count: u32,
+    /// The http status of the response, or `None` if no response was received,
+    /// for example due to a network error or a timeout.
+    pub status: Option<StatusCode>,
+    /// The error the attempt failed with.
+    pub error: Option<&'a StripeError>,
+    /// The value of the `Stripe-Should-Retry` header, if present.
+    pub stripe_should_retry: Option<bool>,
+    /// The value of the `Retry-After` header, if present.
+    pub retry_after: Option<Duration>,
+    /// The time elapsed since the first attempt was started.
+    pub elapsed: Duration,
+}
+
+impl Attempt<'_> {
+    /// Whether the failure is one which is likely to succeed when retried:
+    ///
+    /// - network errors, including timeouts
+    /// - `429 Too Many Requests`
+    /// - `409 Conflict` caused by a `lock_timeout`
+    /// - server errors
+    pub fn is_retryable(&self) -> bool {
+        match self.status {
+            None => true,
+            Some(StatusCode::TooManyRequests) => true,
+            Some(StatusCode::Conflict) => matches!(
+                self.error,
+                Some(StripeError::Stripe(RequestError { code: Some(ErrorCode::LockTimeout), .. }))
+            ),
+            Some(status) => status.is_server_error(),
+        }
+    }
+}
+
+/// A policy deciding if and when a failed request should be retried,
+/// used with [`RequestStrategy::Custom`](crate::RequestStrategy::Custom).
+///
+/// The policy is only consulted after an attempt has failed, and never when
+/// stripe explicitly asks for a request not to be retried. Requests made with
+/// a custom policy carry an idempotency key which is stable across retries.
+///
+/// Policies can be composed using the provided methods:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use stripe::{JitteredBackoff, RequestStrategy, RetryPolicy};
+/// let strategy = RequestStrategy::custom(
+///     JitteredBackoff::new(3).only_retryable_errors().with_deadline(Duration::from_secs(30)),
+/// );
+/// ```
+pub trait RetryPolicy: Debug + Send + Sync {
+    /// Decide whether to retry the request after the given failed attempt,
+    /// and how long to wait before doing so.
+    fn test(&self, attempt: &Attempt) -> Outcome;
+
+    /// Only retry failures which are likely to succeed when retried, as
+    /// determined by [`Attempt::is_retryable`], leaving the rest to this policy.
+    fn only_retryable_errors(self) -> RetryableErrors<Self>
+    where
+        Self: Sized,
+    {
+        RetryableErrors(self)
+    }
+
+    /// Stop retrying when the total time spent on the request, including the
+    /// delay before the next attempt, would exceed the deadline.
+    fn with_deadline(self, deadline: Duration) -> Deadline<Self>
+    where
+        Self: Sized,
+    {
+        Deadline { policy: self, deadline }
+    }
+}
+
+/// Retry up to a number of times, waiting for a random duration between zero
+/// and an exponentially growing delay, capped at a maximum ("full jitter").
+///
+/// If stripe asks for a longer delay using the `Retry-After` header, that is used instead.
+#[derive(Clone, Debug)]
+pub struct JitteredBackoff {
+    /// The maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// The upper bound of the delay before the first retry.
+    pub base: Duration,
+    /// The maximum upper bound of the delay.
+    pub cap: Duration,
+}
+
+impl JitteredBackoff {
+    /// Retry up to `max_retries` times, starting with a delay of up to half a
+    /// second and doubling it up to a maximum of eight seconds.
+    pub fn new(max_retries: u32) -> Self {
+        Self { max_retries, base: Duration::from_millis(500), cap: Duration::from_secs(8) }
+    }
+}
+
+impl RetryPolicy for JitteredBackoff {
+    fn test(&self, attempt: &Attempt) -> Outcome {
+        if attempt.count > self.max_retries {
+            return Outcome::Stop;
+        }
+
+        let exponent = attempt.count.saturating_sub(1).min(31);
+        let ceiling = self.base.saturating_mul(2_u32.pow(exponent)).min(self.cap);
+        let delay = ceiling.mul_f64(random());
+
+        Outcome::Continue(Some(delay.max(attempt.retry_after.unwrap_or_default())))
+    }
+}
+
+/// Retries only the failures which are likely to succeed when retried.
+///
+/// See [`RetryPolicy::only_retryable_errors`].
+#[derive(Clone, Debug)]
+pub struct RetryableErrors<P>(pub P);
+
+impl<P: RetryPolicy> RetryPolicy for RetryableErrors<P> {
+    fn test(&self, attempt: &Attempt) -> Outcome {
+        match attempt.is_retryable() {
+            true => self.0.test(attempt),
+            false => Outcome::Stop,
+        }
+    }
+}
+
+/// Stops retrying once a deadline has passed.
+///
+/// See [`RetryPolicy::with_deadline`].
+#[derive(Clone, Debug)]
+pub struct Deadline<P> {
+    pub policy: P,
+    pub deadline: Duration,
+}
+
+impl<P: RetryPolicy> RetryPolicy for Deadline<P> {
+    fn test(&self, attempt: &Attempt) -> Outcome {
+        match self.policy.test(attempt) {
+            Outcome::Continue(delay)
+                if attempt.elapsed + delay.unwrap_or_default() < self.deadline =>
+            {
+                Outcome::Continue(delay)
+            }
+            _ => Outcome::Stop,
+        }
+    }
+}
+
+/// A random number in `[0, 1)`.
+///
+/// The std hasher is randomly seeded, which is plenty for jittering delays.
+fn random() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http_types::StatusCode;
+
+    use super::{Attempt, JitteredBackoff, RetryPolicy};
+    use crate::client::request_strategy::Outcome;
+    use crate::error::{ErrorCode, RequestError, StripeError};
+
+    #[test]
+    fn jittered_backoff() {
+        let policy = JitteredBackoff::new(3);
+
+        for count in 1..=3 {
+            let ceiling = Duration::from_millis(500 * 2_u64.pow(count - 1));
+            match policy.test(&Attempt { count, ..Default::default() }) {
+                Outcome::Continue(Some(delay)) => assert!(delay <= ceiling),
+                outcome => panic!("unexpected outcome {:?}", outcome),
+            }
+        }
+
+        assert_eq!(policy.test(&Attempt { count: 4, ..Default::default() }), Outcome::Stop);
+    }
+
+    #[test]
+    fn jittered_backoff_cap() {
+        let policy = JitteredBackoff::new(100);
+
+        match policy.test(&Attempt { count: 64, ..Default::default() }) {
+            Outcome::Continue(Some(delay)) => assert!(delay <= Duration::from_secs(8)),
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let policy = JitteredBackoff::new(3);
+        let attempt =
+            Attempt { count: 1, retry_after: Some(Duration::from_secs(10)), ..Default::default() };
+        assert_eq!(policy.test(&attempt), Outcome::Continue(Some(Duration::from_secs(10))));
+    }
+
+    #[test]
+    fn retryable_errors() {
+        let policy = JitteredBackoff::new(3).only_retryable_errors();
+        let test = |status, error| {
+            policy.test(&Attempt { count: 1, status, error, ..Default::default() }) != Outcome::Stop
+        };
+
+        let lock_timeout = StripeError::Stripe(RequestError {
+            code: Some(ErrorCode::LockTimeout),
+            ..Default::default()
+        });
+        let conflict = StripeError::Stripe(RequestError::default());
+
+        assert!(test(None, Some(&StripeError::Timeout)));
+        assert!(test(Some(StatusCode::TooManyRequests), None));
+        assert!(test(Some(StatusCode::InternalServerError), None));
+        assert!(test(Some(StatusCode::Conflict), Some(&lock_timeout)));
+        assert!(!test(Some(StatusCode::Conflict), Some(&conflict)));
+        assert!(!test(Some(StatusCode::BadRequest), None));
+    }
+
+    #[test]
+    fn deadline() {
+        let policy = JitteredBackoff { max_retries: 3, base: Duration::ZERO, cap: Duration::ZERO }
+            .with_deadline(Duration::from_secs(10));
+
+        let attempt =
+            |secs| Attempt { count: 1, elapsed: Duration::from_secs(secs), ..Default::default() };
+        assert_eq!(policy.test(&attempt(5)), Outcome::Continue(Some(Duration::ZERO)));
+        assert_eq!(policy.test(&attempt(10)), Outcome::Stop);
+    }
+}
@@ -0,0 +1,170 @@
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+
+use crate::client::transport::{decode_body, decode_error};
+use crate::StripeError;
+
+/// Parse a response received from stripe into the result of an api call.
+///
+/// Together with [`Client::prepare`](crate::Client::prepare), this allows making
+/// api calls over any http stack.
+///
+/// Unsuccessful responses are decoded into the [`StripeError`] stripe sent back,
+/// exactly as if the request had been sent by the [`Client`](crate::Client).
+pub fn parse_response<T: DeserializeOwned>(
+    response: http::Response<Bytes>,
+) -> Result<T, StripeError> {
+    let (parts, body) = response.into_parts();
+
+    if !parts.status.is_success() {
+        let request_id = parts
+            .headers
+            .get("Request-Id")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        return Err(decode_error(parts.status.as_u16(), request_id, &body));
+    }
+
+    decode_body(&body)
+}
+
+/// Convert a request built by the client into an `http::Request`, reading its body.
+pub(crate) fn into_http(
+    mut request: http_types::Request,
+) -> Result<http::Request<Vec<u8>>, StripeError> {
+    // the bodies built by the client are held in memory, so this does not block
+    let body = futures_lite::future::block_on(request.body_bytes())?;
+    to_http(&request, body)
+}
+
+/// Build an `http::Request` from the head of a request built by the client and the given body.
+pub(crate) fn to_http<B>(
+    request: &http_types::Request,
+    body: B,
+) -> Result<http::Request<B>, StripeError> {
+    let mut builder =
+        http::Request::builder().method(request.method().as_ref()).uri(request.url().as_str());
+    for (name, values) in request.iter() {
+        for value in values.iter() {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+    }
+
+    builder.body(body).map_err(|e| StripeError::ClientError(e.to_string()))
+}
+
+/// Convert an `http::Response` into a response for the client to decode.
+#[cfg(feature = "tower")]
+pub(crate) fn from_http(response: http::Response<Bytes>) -> http_types::Response {
+    let (parts, body) = response.into_parts();
+
+    // statuses unknown to http_types are treated as server errors
+    let status = http_types::StatusCode::try_from(parts.status.as_u16())
+        .unwrap_or(http_types::StatusCode::InternalServerError);
+
+    let mut response = http_types::Response::new(status);
+    for (name, value) in parts.headers.iter() {
+        if let Ok(value) = value.to_str() {
+            response.append_header(name.as_str(), value);
+        }
+    }
+    response.set_body(body.to_vec());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::parse_response;
+    use crate::{
+        Client, CreateCustomer, Customer, Mode, Response, SecretFuture, SecretKey, SecretProvider,
+        StripeError,
+    };
+
+    fn prepare_call<T>(
+        client: &Client,
+        call: impl FnOnce(&Client) -> Response<T>,
+    ) -> Result<http::Request<Vec<u8>>, StripeError> {
+        #[cfg(feature = "async")]
+        return futures_lite::future::block_on(client.prepare(call));
+        #[cfg(feature = "blocking")]
+        return client.prepare(call);
+    }
+
+    #[test]
+    fn prepare() {
+        let client = Client::new("sk_test_123").with_stripe_account("acct_123".parse().unwrap());
+
+        let mut params = CreateCustomer::new();
+        params.email = Some("jenny.rosen@example.com");
+        let request = prepare_call(&client, |client| Customer::create(client, params)).unwrap();
+
+        assert_eq!(request.method(), http::Method::POST);
+        assert_eq!(request.uri(), "https://api.stripe.com/v1/customers");
+        assert_eq!(request.headers()["authorization"], "Bearer sk_test_123");
+        assert_eq!(request.headers()["stripe-account"], "acct_123");
+        assert_eq!(request.headers()["content-type"], "application/x-www-form-urlencoded");
+        assert_eq!(request.body(), b"email=jenny.rosen%40example.com");
+    }
+
+    #[test]
+    fn prepare_serialize_error() {
+        let client = Client::new("sk_test_123");
+        let result = prepare_call(&client, |client| client.get_query::<(), _>("customers", 5));
+        assert!(matches!(result, Err(StripeError::QueryStringSerialize(_))), "{:?}", result);
+    }
+
+    #[test]
+    fn prepare_checks_mode() {
+        let client = Client::new("sk_test_123").with_expected_mode(Mode::Live);
+        let result = prepare_call(&client, |client| client.get::<serde_json::Value>("balance"));
+        assert!(matches!(result, Err(StripeError::Config(_))), "{:?}", result);
+    }
+
+    /// Provides its key from a task on the current runtime, which only completes
+    /// if that runtime is not blocked waiting for it.
+    struct Spawning;
+
+    impl SecretProvider for Spawning {
+        fn fetch(&self) -> SecretFuture<'_> {
+            Box::pin(async {
+                let key = tokio::spawn(async { SecretKey::new("sk_test_456") });
+                key.await.map_err(|e| StripeError::ClientError(e.to_string()))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn prepare_with_secret_provider() {
+        let client = Client::new("").with_secret_provider(Spawning, std::time::Duration::MAX);
+
+        #[cfg(feature = "async")]
+        let request =
+            client.prepare(|client| client.get::<serde_json::Value>("balance")).await.unwrap();
+        #[cfg(feature = "blocking")]
+        let request = client.prepare(|client| client.get::<serde_json::Value>("balance")).unwrap();
+
+        assert_eq!(request.headers()["authorization"], "Bearer sk_test_456");
+    }
+
+    #[test]
+    fn parse() {
+        let response = http::Response::new(Bytes::from(r#"{"id": "cus_123"}"#));
+        let value: serde_json::Value = parse_response(response).unwrap();
+        assert_eq!(value["id"], "cus_123");
+
+        let response = http::Response::builder()
+            .status(404)
+            .header("Request-Id", "req_123")
+            .body(Bytes::from(r#"{"error": {"type": "invalid_request_error"}}"#))
+            .unwrap();
+        match parse_response::<serde_json::Value>(response) {
+            Err(StripeError::Stripe(err)) => {
+                assert_eq!(err.http_status, 404);
+                assert_eq!(err.request_id.as_deref(), Some("req_123"));
+            }
+            result => panic!("expected a stripe error, got {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use zeroize::Zeroizing;
+
+use crate::StripeError;
+
+/// A secret api key, which is zeroized when dropped and never printed.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretKey(Zeroizing<String>);
+
+impl SecretKey {
+    /// Wrap a secret key.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(Zeroizing::new(key.into()))
+    }
+
+    /// Get the key itself, for example to send it to stripe.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+impl From<String> for SecretKey {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<&str> for SecretKey {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<&String> for SecretKey {
+    fn from(key: &String) -> Self {
+        Self::new(key.as_str())
+    }
+}
+
+impl From<Box<str>> for SecretKey {
+    fn from(key: Box<str>) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<Cow<'_, str>> for SecretKey {
+    fn from(key: Cow<'_, str>) -> Self {
+        Self::new(key)
+    }
+}
+
+/// The future returned by [`SecretProvider::fetch`].
+pub type SecretFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<SecretKey, StripeError>> + Send + 'a>>;
+
+/// A source of secret keys, such as a vault in which keys are rotated.
+///
+/// Set one on a client with [`Client::with_secret_provider`](crate::Client::with_secret_provider).
+/// The client caches the key it fetches, and fetches it again once it is too
+/// old, or as soon as stripe rejects it as invalid or expired.
+///
+/// ```no_run
+/// # use stripe::{Client, SecretFuture, SecretKey, SecretProvider};
+/// # use std::time::Duration;
+/// struct Vault;
+///
+/// impl SecretProvider for Vault {
+///     fn fetch(&self) -> SecretFuture<'_> {
+///         Box::pin(async { Ok(SecretKey::new(std::env::var("STRIPE_SECRET_KEY").unwrap())) })
+///     }
+/// }
+///
+/// let client = Client::new("").with_secret_provider(Vault, Duration::from_secs(300));
+/// ```
+pub trait SecretProvider: Send + Sync {
+    /// Fetch the current secret key.
+    fn fetch(&self) -> SecretFuture<'_>;
+}
+
+/// A secret provider, along with the last key it provided.
+pub(crate) struct CachedSecret {
+    provider: Box<dyn SecretProvider>,
+    max_age: Duration,
+    cached: Mutex<Option<(SecretKey, Instant)>>,
+    /// Held while fetching a key, so that concurrent requests wait for a single fetch.
+    fetching: futures_util::lock::Mutex<()>,
+}
+
+impl CachedSecret {
+    pub fn new(provider: impl SecretProvider + 'static, max_age: Duration) -> Self {
+        Self {
+            provider: Box::new(provider),
+            max_age,
+            cached: Mutex::default(),
+            fetching: futures_util::lock::Mutex::new(()),
+        }
+    }
+
+    /// Get the cached key, fetching a new one if it is missing or too old.
+    pub async fn get(&self) -> Result<SecretKey, StripeError> {
+        if let Some(key) = self.fresh() {
+            return Ok(key);
+        }
+
+        let _fetching = self.fetching.lock().await;
+        // the key may have been fetched while waiting for the lock
+        match self.fresh() {
+            Some(key) => Ok(key),
+            None => self.fetch().await,
+        }
+    }
+
+    /// Fetch a new key to replace the given one, which stripe rejected.
+    ///
+    /// If the cached key was already replaced, for example by a concurrent
+    /// request which was also rejected, the replacement is returned instead.
+    pub async fn refresh(&self, rejected: &SecretKey) -> Result<SecretKey, StripeError> {
+        let _fetching = self.fetching.lock().await;
+        match self.cached().map(|(key, _)| key) {
+            Some(key) if key != *rejected => Ok(key),
+            _ => self.fetch().await,
+        }
+    }
+
+    fn cached(&self) -> Option<(SecretKey, Instant)> {
+        self.cached.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn fresh(&self) -> Option<SecretKey> {
+        self.cached().filter(|(_, fetched)| fetched.elapsed() < self.max_age).map(|(key, _)| key)
+    }
+
+    /// Fetch a new key, replacing the cached one. Only called with `fetching` held.
+    async fn fetch(&self) -> Result<SecretKey, StripeError> {
+        let key = self.provider.fetch().await?;
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((key.clone(), Instant::now()));
+        Ok(key)
+    }
+}
+
+impl fmt::Debug for CachedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedSecret").field("max_age", &self.max_age).finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::{CachedSecret, SecretFuture, SecretKey, SecretProvider};
+    use crate::{Client, Transport, TransportFuture};
+
+    /// Provides `sk_test_1`, then `sk_test_2` and so on.
+    #[derive(Clone, Default)]
+    struct Rotating(Arc<AtomicU32>);
+
+    impl SecretProvider for Rotating {
+        fn fetch(&self) -> SecretFuture<'_> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move { Ok(SecretKey::new(format!("sk_test_{}", n))) })
+        }
+    }
+
+    /// Rejects all keys but `sk_test_2`, recording the keys it is sent.
+    #[derive(Clone, Default)]
+    struct Stripe(Arc<Mutex<Vec<String>>>);
+
+    impl Transport for Stripe {
+        fn send(&self, request: http_types::Request) -> TransportFuture<'_> {
+            let authorization = request.header("authorization").unwrap().last().to_string();
+            self.0.lock().unwrap().push(authorization.clone());
+            Box::pin(async move {
+                let mut response = http_types::Response::new(200);
+                if authorization != "Bearer sk_test_2" {
+                    response.set_status(http_types::StatusCode::Unauthorized);
+                    response.set_body(r#"{"error": {"type": "invalid_request_error", "code": "api_key_expired"}}"#);
+                } else {
+                    response.set_body("{}");
+                }
+                Ok(response)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn rotation() {
+        let provider = Rotating::default();
+        let stripe = Stripe::default();
+        let client = Client::new("")
+            .with_transport(stripe.clone())
+            .with_secret_provider(provider.clone(), Duration::from_secs(60));
+
+        // the expired key is refreshed once, and the new one is cached
+        client.get::<serde_json::Value>("balance").await.unwrap();
+        client.clone().get::<serde_json::Value>("balance").await.unwrap();
+        assert_eq!(provider.0.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            *stripe.0.lock().unwrap(),
+            ["Bearer sk_test_1", "Bearer sk_test_2", "Bearer sk_test_2"]
+        );
+
+        // a key which is rejected after refreshing is not refreshed again
+        let client = Client::new("")
+            .with_transport(stripe.clone())
+            .with_secret_provider(Rotating(Arc::new(AtomicU32::new(5))), Duration::from_secs(60));
+        assert!(client.get::<serde_json::Value>("balance").await.is_err());
+        assert_eq!(stripe.0.lock().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn single_fetch() {
+        /// Like `Rotating`, but yields before answering so that fetches overlap.
+        #[derive(Clone, Default)]
+        struct Slow(Rotating);
+
+        impl SecretProvider for Slow {
+            fn fetch(&self) -> SecretFuture<'_> {
+                Box::pin(async {
+                    tokio::task::yield_now().await;
+                    self.0.fetch().await
+                })
+            }
+        }
+
+        let provider = Slow::default();
+        let secret = CachedSecret::new(provider.clone(), Duration::from_secs(60));
+        let keys = futures_util::future::join_all((0..5).map(|_| secret.get())).await;
+        assert_eq!(provider.0 .0.load(Ordering::SeqCst), 1);
+        assert!(keys.iter().all(|key| key.as_ref().unwrap().expose() == "sk_test_1"));
+
+        // requests rejected with the same key share a single refresh
+        let rejected = SecretKey::new("sk_test_1");
+        let keys = futures_util::future::join_all((0..5).map(|_| secret.refresh(&rejected))).await;
+        assert_eq!(provider.0 .0.load(Ordering::SeqCst), 2);
+        assert!(keys.iter().all(|key| key.as_ref().unwrap().expose() == "sk_test_2"));
+    }
+
+    #[test]
+    fn redacted() {
+        let key = SecretKey::new("sk_test_123");
+        assert_eq!(format!("{:?}", key), "SecretKey(..)");
+        assert_eq!(key.expose(), "sk_test_123");
+    }
+
+    #[test]
+    fn conversions() {
+        // every type which `Client::new` used to accept as an `Into<String>`
+        let key = String::from("sk_test_123");
+        assert_eq!(SecretKey::from(&key).expose(), "sk_test_123");
+        assert_eq!(SecretKey::from(key.clone().into_boxed_str()).expose(), "sk_test_123");
+        assert_eq!(SecretKey::from(std::borrow::Cow::from(&*key)).expose(), "sk_test_123");
+        assert_eq!(SecretKey::from(key).expose(), "sk_test_123");
+    }
+}
@@ -0,0 +1,252 @@
+//! Integration with [`tower`](https://docs.rs/tower), enabled by the `tower` feature.
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::client::sans_io::{from_http, parse_response, to_http};
+use crate::client::transport::{Transport, TransportFuture};
+use crate::StripeError;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A [`Transport`] which sends requests over a [`tower::Service`](Service).
+///
+/// This allows wrapping the http layer of a [`Client`](crate::Client) in any tower
+/// middleware, such as timeouts, load shedding or concurrency limits. Failures of
+/// the service are retried according to the [`RequestStrategy`](crate::RequestStrategy)
+/// of the client, like any other network failure.
+///
+/// ```rust,ignore
+/// use stripe::{Client, HyperTransport, ServiceTransport};
+///
+/// let service = tower::ServiceBuilder::new()
+///     .load_shed()
+///     .concurrency_limit(16)
+///     .service(HyperTransport::builder().pool_max_idle_per_host(16).build());
+/// let client = Client::new("sk_test_123").with_transport(ServiceTransport::new(service));
+/// ```
+pub struct ServiceTransport<S> {
+    service: Mutex<S>,
+}
+
+impl<S> ServiceTransport<S> {
+    /// Send requests over the given service, which is cloned for each request.
+    pub fn new(service: S) -> Self {
+        Self { service: Mutex::new(service) }
+    }
+}
+
+impl<S> Transport for ServiceTransport<S>
+where
+    S: Service<http::Request<Bytes>, Response = http::Response<Bytes>> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    fn send(&self, mut request: http_types::Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let body = request.body_bytes().await?;
+            self.send_buffered(&request, body.into()).await
+        })
+    }
+
+    fn send_buffered(&self, request: &http_types::Request, body: Bytes) -> TransportFuture<'_> {
+        let request = to_http(request, body);
+        let service = self
+            .service
+            .lock()
+            .map(|service| service.clone())
+            .map_err(|_| StripeError::ClientError("the service was poisoned".to_string()));
+        Box::pin(async move {
+            let (mut service, request) = (service?, request?);
+
+            futures_lite::future::poll_fn(|cx| service.poll_ready(cx)).await.map_err(into_error)?;
+            let response = service.call(request).await.map_err(into_error)?;
+            Ok(from_http(response))
+        })
+    }
+}
+
+/// A [`tower::Service`](Service) which sends requests over an inner service, and
+/// parses their responses into the result of an api call with [`parse_response`].
+///
+/// Together with [`Client::prepare`](crate::Client::prepare), this allows making
+/// api calls entirely through a tower stack.
+///
+/// ```rust,ignore
+/// use stripe::{Client, Customer, CustomerId, HyperTransport, ParseResponseLayer};
+/// use tower::ServiceExt;
+///
+/// let client = Client::new("sk_test_123");
+/// let id: CustomerId = "cus_123".parse()?;
+/// let request = client.prepare(|client| Customer::retrieve(client, &id, &[])).await?;
+///
+/// let customer: Customer = tower::ServiceBuilder::new()
+///     .layer(ParseResponseLayer::new())
+///     .service(HyperTransport::new())
+///     .oneshot(request.map(Into::into))
+///     .await?;
+/// ```
+pub struct ParseResponse<S, T> {
+    inner: S,
+    _response: PhantomData<fn() -> T>,
+}
+
+impl<S, T> ParseResponse<S, T> {
+    /// Parse the responses of the given service.
+    pub fn new(inner: S) -> Self {
+        Self { inner, _response: PhantomData }
+    }
+}
+
+impl<S: Clone, T> Clone for ParseResponse<S, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone())
+    }
+}
+
+impl<S, T> Service<http::Request<Bytes>> for ParseResponse<S, T>
+where
+    S: Service<http::Request<Bytes>, Response = http::Response<Bytes>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    T: DeserializeOwned,
+{
+    type Response = T;
+    type Error = StripeError;
+    type Future = Pin<Box<dyn Future<Output = Result<T, StripeError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(into_error)
+    }
+
+    fn call(&mut self, request: http::Request<Bytes>) -> Self::Future {
+        let response = self.inner.call(request);
+        Box::pin(async move { parse_response(response.await.map_err(into_error)?) })
+    }
+}
+
+/// A [`tower::Layer`](Layer) wrapping services in a [`ParseResponse`].
+pub struct ParseResponseLayer<T> {
+    _response: PhantomData<fn() -> T>,
+}
+
+impl<T> ParseResponseLayer<T> {
+    /// Create a layer parsing responses into `T`.
+    pub fn new() -> Self {
+        Self { _response: PhantomData }
+    }
+}
+
+impl<T> Default for ParseResponseLayer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for ParseResponseLayer<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<S, T> Layer<S> for ParseResponseLayer<T> {
+    type Service = ParseResponse<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ParseResponse::new(inner)
+    }
+}
+
+/// Convert the error of a service, keeping any [`StripeError`] it wraps.
+fn into_error(error: impl Into<BoxError>) -> StripeError {
+    match error.into().downcast::<StripeError>() {
+        Ok(error) => *error,
+        Err(error) => StripeError::ClientError(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use tower::{service_fn, ServiceExt};
+
+    use super::ParseResponseLayer;
+    use crate::StripeError;
+
+    #[tokio::test]
+    async fn parse_response() {
+        let service = tower::ServiceBuilder::new().layer(ParseResponseLayer::new()).service(
+            service_fn(|request: http::Request<Bytes>| async move {
+                let body = match request.uri().path() {
+                    "/v1/customers/cus_123" => r#"{"id": "cus_123"}"#,
+                    _ => return Err(StripeError::ClientError("unexpected request".to_string())),
+                };
+                Ok::<_, StripeError>(http::Response::new(Bytes::from(body)))
+            }),
+        );
+
+        let request = http::Request::get("https://api.stripe.com/v1/customers/cus_123")
+            .body(Bytes::new())
+            .unwrap();
+        let value: serde_json::Value = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(value["id"], "cus_123");
+
+        let request =
+            http::Request::get("https://api.stripe.com/v1/missing").body(Bytes::new()).unwrap();
+        let result = service.oneshot(request).await;
+        assert!(matches!(result, Err(StripeError::ClientError(_))), "{:?}", result);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn transport() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        use super::ServiceTransport;
+        use crate::Client;
+
+        let tries = Arc::new(AtomicU32::new(0));
+        let service = service_fn({
+            let tries = tries.clone();
+            move |request: http::Request<Bytes>| {
+                let tries = tries.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    assert_eq!(request.headers()["authorization"], "Bearer sk_test_123");
+                    assert_eq!(request.body(), "email=jenny.rosen%40example.com");
+                    let response = match tries {
+                        0 => http::Response::builder().status(500).body(Bytes::from("{}")),
+                        _ => http::Response::builder()
+                            .header("Request-Id", "req_123")
+                            .body(Bytes::from(r#"{"id": "cus_123"}"#)),
+                    };
+                    Ok::<_, StripeError>(response.unwrap())
+                }
+            }
+        });
+        let client = Client::new("sk_test_123")
+            .with_strategy(crate::RequestStrategy::Retry(2))
+            .with_transport(ServiceTransport::new(service));
+
+        let (value, meta) = client
+            .capture_meta(|client| {
+                client.post_form::<serde_json::Value, _>(
+                    "customers",
+                    serde_json::json!({"email": "jenny.rosen@example.com"}),
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(value["id"], "cus_123");
+        assert_eq!(meta.request_id(), Some("req_123"));
+        assert_eq!(tries.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,12 +1,27 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use http_types::{Body, Method, Request, Url};
 use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(feature = "async")]
+use crate::client::response_future::ResponseFuture;
 use crate::{
-    client::{request_strategy::RequestStrategy, BaseClient, Response},
+    client::{
+        hooks::Hooks,
+        mode::Mode,
+        rate_limit::RateLimiter,
+        request_options::RequestOptions,
+        request_strategy::RequestStrategy,
+        response_meta::{ResponseMeta, ResponseMetaSlot},
+        secret::{CachedSecret, SecretKey, SecretProvider},
+        transport::{authorize, RequestConfig},
+        BaseClient, Response, Transport,
+    },
     config::err,
     generated::core::version::VERSION,
     params::AppInfo,
-    AccountId, ApplicationId, Headers, StripeError,
+    AccountId, ApiVersion, ApplicationId, Headers, StripeError,
 };
 
 static USER_AGENT: &str = concat!("Stripe/v1 RustBindings/", env!("CARGO_PKG_VERSION"));
@@ -14,9 +29,24 @@ static USER_AGENT: &str = concat!("Stripe/v1 RustBindings/", env!("CARGO_PKG_VER
 #[derive(Clone)]
 pub struct Client {
     client: crate::client::BaseClient,
-    secret_key: String,
+    secret_key: SecretKey,
+    secret_provider: Option<Arc<CachedSecret>>,
     headers: Headers,
     strategy: RequestStrategy,
+    /// Overrides of the settings above, applied to each request when it is made.
+    options: RequestOptions,
+    /// The idempotency key of the options, which is taken by the first request sent.
+    idempotency_key: Option<Arc<Mutex<Option<String>>>>,
+    #[cfg(feature = "uuid")]
+    automatic_idempotency_keys: bool,
+    timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    meta: Option<ResponseMetaSlot>,
+    rate_limiter: Option<RateLimiter>,
+    mode: Option<Mode>,
+    hooks: Arc<Hooks>,
+    #[cfg(feature = "sans-io")]
+    prepared: Option<PreparedSlot>,
     app_info: Option<AppInfo>,
     api_base: Url,
     api_root: String,
@@ -24,18 +54,20 @@ pub struct Client {
 
 impl Client {
     /// Create a new account with the given secret key.
-    pub fn new(secret_key: impl Into<String>) -> Self {
+    pub fn new(secret_key: impl Into<SecretKey>) -> Self {
         Self::from_url("https://api.stripe.com/", secret_key)
     }
 
     /// Create a new account pointed at a specific URL. This is useful for testing.
     ///
     /// # Panics
-    /// If the url can't be parsed
-    pub fn from_url<'a>(url: impl Into<&'a str>, secret_key: impl Into<String>) -> Self {
+    /// If the url can't be parsed. Use a [`ClientBuilder`](crate::ClientBuilder)
+    /// to handle this error instead.
+    pub fn from_url<'a>(url: impl Into<&'a str>, secret_key: impl Into<SecretKey>) -> Self {
         Client {
             client: BaseClient::new(),
             secret_key: secret_key.into(),
+            secret_provider: None,
             headers: Headers {
                 stripe_version: VERSION,
                 user_agent: USER_AGENT.to_string(),
@@ -43,6 +75,18 @@ impl Client {
                 stripe_account: None,
             },
             strategy: RequestStrategy::Once,
+            options: RequestOptions::default(),
+            idempotency_key: None,
+            #[cfg(feature = "uuid")]
+            automatic_idempotency_keys: false,
+            timeout: None,
+            total_timeout: None,
+            meta: None,
+            rate_limiter: None,
+            mode: None,
+            hooks: Arc::default(),
+            #[cfg(feature = "sans-io")]
+            prepared: None,
             app_info: None,
             api_base: Url::parse(url.into()).expect("invalid url"),
             api_root: "v1".to_string(),
@@ -61,12 +105,202 @@ impl Client {
         self
     }
 
+    /// Set the version of the api used by every request, sent as the `Stripe-Version` header.
+    pub(crate) fn with_stripe_version(mut self, version: ApiVersion) -> Self {
+        self.headers.stripe_version = version;
+        self
+    }
+
     /// Set the request strategy for the client.
     pub fn with_strategy(mut self, strategy: RequestStrategy) -> Self {
         self.strategy = strategy;
         self
     }
 
+    /// Send an idempotency key with every `POST` request, as the official stripe libraries do.
+    ///
+    /// A random key is generated for each call, and is reused when the request is
+    /// retried. Keys set with [`RequestOptions::idempotency_key`] or by the
+    /// [`RequestStrategy`] take precedence. To make requests exactly-once across
+    /// process restarts, derive the key from a business key using
+    /// [`RequestStrategy::derive_key`] instead.
+    #[cfg(feature = "uuid")]
+    pub fn with_automatic_idempotency_keys(mut self) -> Self {
+        self.automatic_idempotency_keys = true;
+        self
+    }
+
+    /// Limit the rate at which this client sends requests to stripe.
+    ///
+    /// Requests which would exceed the limits are delayed until there is capacity
+    /// for them. The limiter is shared with all clones of this client.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Guard against using this client with a key for the wrong mode, such as
+    /// a live key in a staging deployment.
+    ///
+    /// Requests made with a key which is not a secret or restricted key for the
+    /// given mode fail with a [`StripeError::Config`], without being sent. In
+    /// debug builds, responses holding objects whose `livemode` does not match
+    /// fail in the same way. To reject a mismatched key when the client is
+    /// created instead, see [`ClientBuilder::expected_mode`](crate::ClientBuilder::expected_mode).
+    pub fn with_expected_mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Call the given function with every request before it is sent, for example
+    /// to add a correlation id header.
+    ///
+    /// The function is called once per request, after its headers and idempotency
+    /// key are set and before its first attempt, so any changes it makes are kept
+    /// when the request is retried. Hooks are shared with all clones of this client.
+    ///
+    /// ```no_run
+    /// # use stripe::Client;
+    /// let client = Client::new("sk_test_123").with_before_request(|request| {
+    ///     request.insert_header("X-Correlation-Id", "d0d3c1b4");
+    /// });
+    /// ```
+    pub fn with_before_request(
+        mut self,
+        hook: impl Fn(&mut Request) + Send + Sync + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.hooks).before_request.push(Arc::new(hook));
+        self
+    }
+
+    /// Call the given function with every response received, along with the time
+    /// taken by the attempt which received it.
+    ///
+    /// The function is called for each attempt, so a request which is retried
+    /// calls it several times. Hooks are shared with all clones of this client.
+    pub fn with_after_response(
+        mut self,
+        hook: impl Fn(&ResponseMeta, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.hooks).after_response.push(Arc::new(hook));
+        self
+    }
+
+    /// Call the given function with the error of every request which fails,
+    /// once the [`RequestStrategy`] gives up on it.
+    ///
+    /// Hooks are shared with all clones of this client.
+    pub fn with_on_error(mut self, hook: impl Fn(&StripeError) + Send + Sync + 'static) -> Self {
+        Arc::make_mut(&mut self.hooks).on_error.push(Arc::new(hook));
+        self
+    }
+
+    /// Set the maximum time a single attempt at a request may take, including
+    /// reading the response body.
+    ///
+    /// When a request times out it is retried according to the [`RequestStrategy`]
+    /// like any other network failure, and [`StripeError::Timeout`] is returned if
+    /// it runs out of attempts.
+    ///
+    /// To use a different timeout for a single call, see [`RequestOptions::timeout`].
+    ///
+    /// To bound the time taken by a call including its retries, see
+    /// [`Client::with_total_timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum time a request may take, including all of its retries and
+    /// the delays between them.
+    ///
+    /// [`StripeError::Timeout`] is returned if the request has not completed in
+    /// time. Without either timeout, the blocking client gives up on a call after
+    /// 30 seconds, and the async clients wait for as long as the retries take.
+    pub fn with_total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Get a copy of this client with the given options applied, for use in a single request.
+    ///
+    /// The returned client shares its transport with this one, so this is cheap
+    /// to do for every request, and this client is left unchanged.
+    ///
+    /// The options are applied to every request made by the returned client, and
+    /// take precedence over its other settings, except for the idempotency key.
+    /// It belongs to a single call, so it is sent with the first request made by
+    /// the returned client or any of its clones, and reused only when that request
+    /// is retried by the [`RequestStrategy`]. To make a failed call again with the
+    /// same key, call this again with the same options.
+    ///
+    /// ```no_run
+    /// # use stripe::{Client, Customer, CustomerId, RequestOptions};
+    /// # async fn run(client: &Client, id: &CustomerId) {
+    /// let options = RequestOptions { stripe_account: Some("acct_123".parse().unwrap()), ..Default::default() };
+    /// let customer = Customer::retrieve(&client.with_options(options), id, &[]);
+    /// # }
+    /// ```
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        let mut client = self.clone();
+        let current = &mut client.options;
+        current.stripe_account = options.stripe_account.or(current.stripe_account.take());
+        current.stripe_version = options.stripe_version.or(current.stripe_version.take());
+        current.strategy = options.strategy.or(current.strategy.take());
+        current.timeout = options.timeout.or(current.timeout.take());
+        if let Some(key) = options.idempotency_key {
+            client.idempotency_key = Some(Arc::new(Mutex::new(Some(key))));
+        }
+        client
+    }
+
+    /// Get a copy of this client which authenticates with a different secret key,
+    /// for example to make requests for another platform.
+    ///
+    /// The returned client shares its transport with this one, and with it any
+    /// pooled connections, so this is cheap to do for each of many tenants. All
+    /// other configuration is kept, including the rate limiter and the hooks.
+    /// See [`TenantRegistry`](crate::TenantRegistry) to keep track of such clients.
+    pub fn with_secret_key(&self, secret_key: impl Into<SecretKey>) -> Self {
+        let mut client = self.clone();
+        client.secret_key = secret_key.into();
+        client.secret_provider = None;
+        client
+    }
+
+    /// Get the secret key for each request from the given provider, instead of
+    /// using the key the client was created with.
+    ///
+    /// The key is cached for up to `max_age`, and is shared with all clones of
+    /// this client. If stripe rejects a key as invalid or expired, a new one is
+    /// fetched and the request is sent once more with it.
+    pub fn with_secret_provider(
+        self,
+        provider: impl SecretProvider + 'static,
+        max_age: Duration,
+    ) -> Self {
+        self.with_cached_secret(Arc::new(CachedSecret::new(provider, max_age)))
+    }
+
+    pub(crate) fn with_cached_secret(mut self, secret: Arc<CachedSecret>) -> Self {
+        self.secret_provider = Some(secret);
+        self
+    }
+
+    /// Send all requests made by this client over the given transport,
+    /// instead of the default one for the selected runtime.
+    ///
+    /// Connection settings, such as the connect timeout, are part of the transport,
+    /// see [`HyperTransportBuilder`](crate::HyperTransportBuilder) to configure them,
+    /// or [`ClientBuilder::connect_timeout`](crate::ClientBuilder::connect_timeout).
+    ///
+    /// Retries and error handling are still performed by the client, see
+    /// [`Transport`] for details.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.client = BaseClient::with_transport(transport);
+        self
+    }
+
     /// Set the application info for the client.
     ///
     /// It is recommended that applications set this so that
@@ -84,10 +318,166 @@ impl Client {
         self
     }
 
+    /// Make a request, getting hold of the metadata of the response alongside its result.
+    ///
+    /// The given function is called with a copy of this client which records the
+    /// metadata of the response, such as its `Request-Id` header, so this works
+    /// with any api method. If the function makes several requests, the metadata
+    /// is that of the last response.
+    ///
+    /// ```no_run
+    /// # use stripe::{Client, CreateCustomer, Customer, StripeError};
+    /// # async fn run(client: &Client) -> Result<(), StripeError> {
+    /// let (customer, meta) =
+    ///     client.capture_meta(|client| Customer::create(client, CreateCustomer::new())).await?;
+    /// println!("created {} in request {:?}", customer.id, meta.request_id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn capture_meta<T: Send + 'static>(
+        &self,
+        request: impl FnOnce(&Client) -> Response<T>,
+    ) -> Response<(T, ResponseMeta)> {
+        let (client, slot) = self.with_meta_slot();
+        let response = request(&client);
+        ResponseFuture::new(async move {
+            let value = response.await?;
+            Ok((value, take_meta(&slot)?))
+        })
+    }
+
+    /// Make a request, getting hold of the metadata of the response alongside its result.
+    ///
+    /// The given function is called with a copy of this client which records the
+    /// metadata of the response, such as its `Request-Id` header, so this works
+    /// with any api method. If the function makes several requests, the metadata
+    /// is that of the last response.
+    ///
+    /// ```no_run
+    /// # use stripe::{Client, CreateCustomer, Customer, StripeError};
+    /// # fn run(client: &Client) -> Result<(), StripeError> {
+    /// let (customer, meta) =
+    ///     client.capture_meta(|client| Customer::create(client, CreateCustomer::new()))?;
+    /// println!("created {} in request {:?}", customer.id, meta.request_id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn capture_meta<T>(
+        &self,
+        request: impl FnOnce(&Client) -> Response<T>,
+    ) -> Response<(T, ResponseMeta)> {
+        let (client, slot) = self.with_meta_slot();
+        let value = request(&client)?;
+        Ok((value, take_meta(&slot)?))
+    }
+
+    /// Build the http request an api call would send, without sending it.
+    ///
+    /// The given function is called with a copy of this client which records the
+    /// request instead of sending it, so this works with any api method. This is
+    /// useful to log exactly what would be sent in a dry run, or to make requests
+    /// over an http stack of your own, parsing the responses with
+    /// [`parse_response`](crate::parse_response).
+    ///
+    /// The request is authorized and checked against the expected [`Mode`] exactly
+    /// as it would be when sent, fetching the key from the secret provider if
+    /// there is one.
+    ///
+    /// ```
+    /// # use stripe::{Client, CreateCustomer, Customer, StripeError};
+    /// # async fn run(client: &Client) -> Result<(), StripeError> {
+    /// let request =
+    ///     client.prepare(|client| Customer::create(client, CreateCustomer::new())).await?;
+    /// println!("{} {}", request.method(), request.uri());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(feature = "sans-io", feature = "async"))]
+    pub async fn prepare<T>(
+        &self,
+        call: impl FnOnce(&Client) -> Response<T>,
+    ) -> Result<http::Request<Vec<u8>>, StripeError> {
+        let (client, slot) = self.with_prepared_slot();
+        let request = take_prepared(&slot, call(&client).await)?;
+        let key = match &self.secret_provider {
+            Some(secret) => Some(secret.get().await?),
+            None => None,
+        };
+        self.finish_prepared(request, key)
+    }
+
+    /// Build the http request an api call would send, without sending it.
+    ///
+    /// The given function is called with a copy of this client which records the
+    /// request instead of sending it, so this works with any api method. This is
+    /// useful to log exactly what would be sent in a dry run, or to make requests
+    /// over an http stack of your own, parsing the responses with
+    /// [`parse_response`](crate::parse_response).
+    ///
+    /// The request is authorized and checked against the expected [`Mode`] exactly
+    /// as it would be when sent, fetching the key from the secret provider if
+    /// there is one.
+    ///
+    /// ```
+    /// # use stripe::{Client, CreateCustomer, Customer, StripeError};
+    /// # fn run(client: &Client) -> Result<(), StripeError> {
+    /// let request = client.prepare(|client| Customer::create(client, CreateCustomer::new()))?;
+    /// println!("{} {}", request.method(), request.uri());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(feature = "sans-io", feature = "blocking"))]
+    pub fn prepare<T>(
+        &self,
+        call: impl FnOnce(&Client) -> Response<T>,
+    ) -> Result<http::Request<Vec<u8>>, StripeError> {
+        let (client, slot) = self.with_prepared_slot();
+        let request = take_prepared(&slot, call(&client))?;
+        // providers may fetch their keys over the network, so this is done on the
+        // runtime of the blocking client like any request
+        let key = match self.secret_provider.clone() {
+            Some(secret) => Some(self.client.block_on(async move { secret.get().await })?),
+            None => None,
+        };
+        self.finish_prepared(request, key)
+    }
+
+    #[cfg(feature = "sans-io")]
+    fn with_prepared_slot(&self) -> (Client, PreparedSlot) {
+        let slot = PreparedSlot::default();
+        let mut client = self.clone();
+        client.prepared = Some(slot.clone());
+        (client, slot)
+    }
+
+    /// Authorize a prepared request with the key from the secret provider, if
+    /// there is one, and check it is in the expected mode.
+    #[cfg(feature = "sans-io")]
+    fn finish_prepared(
+        &self,
+        mut request: Request,
+        key: Option<SecretKey>,
+    ) -> Result<http::Request<Vec<u8>>, StripeError> {
+        if let Some(key) = key {
+            authorize(&mut request, &key);
+        }
+        crate::client::transport::check_mode(&request, self.mode)?;
+        crate::client::sans_io::into_http(request)
+    }
+
+    fn with_meta_slot(&self) -> (Client, ResponseMetaSlot) {
+        let slot = ResponseMetaSlot::default();
+        let mut client = self.clone();
+        client.meta = Some(slot.clone());
+        (client, slot)
+    }
+
     /// Make a `GET` http request with just a path
     pub fn get<T: DeserializeOwned + Send + 'static>(&self, path: &str) -> Response<T> {
         let url = self.url(path);
-        self.client.execute::<T>(self.create_request(Method::Get, url), &self.strategy)
+        self.execute::<T>(self.create_request(Method::Get, url))
     }
 
     /// Make a `GET` http request with url query parameters
@@ -96,17 +486,33 @@ impl Client {
         path: &str,
         params: P,
     ) -> Response<T> {
+        self.get_query_map(path, params, |value| value)
+    }
+
+    /// Make a `GET` http request with url query parameters, and map its result
+    /// within the same future.
+    pub(crate) fn get_query_map<T, U, P>(
+        &self,
+        path: &str,
+        params: P,
+        f: impl FnOnce(T) -> U + Send + 'static,
+    ) -> Response<U>
+    where
+        T: DeserializeOwned + Send + 'static,
+        U: Send + 'static,
+        P: Serialize,
+    {
         let url = match self.url_with_params(path, params) {
             Err(e) => return err(e),
             Ok(ok) => ok,
         };
-        self.client.execute::<T>(self.create_request(Method::Get, url), &self.strategy)
+        self.execute_map(self.create_request(Method::Get, url), f)
     }
 
     /// Make a `DELETE` http request with just a path
     pub fn delete<T: DeserializeOwned + Send + 'static>(&self, path: &str) -> Response<T> {
         let url = self.url(path);
-        self.client.execute::<T>(self.create_request(Method::Delete, url), &self.strategy)
+        self.execute::<T>(self.create_request(Method::Delete, url))
     }
 
     /// Make a `DELETE` http request with url query parameters
@@ -119,13 +525,13 @@ impl Client {
             Err(e) => return err(e),
             Ok(ok) => ok,
         };
-        self.client.execute::<T>(self.create_request(Method::Delete, url), &self.strategy)
+        self.execute::<T>(self.create_request(Method::Delete, url))
     }
 
     /// Make a `POST` http request with just a path
     pub fn post<T: DeserializeOwned + Send + 'static>(&self, path: &str) -> Response<T> {
         let url = self.url(path);
-        self.client.execute::<T>(self.create_request(Method::Post, url), &self.strategy)
+        self.execute::<T>(self.create_request(Method::Post, url))
     }
 
     /// Make a `POST` http request with urlencoded body
@@ -137,23 +543,131 @@ impl Client {
         path: &str,
         form: F,
     ) -> Response<T> {
-        let url = self.url(path);
-        let mut req = self.create_request(Method::Post, url);
+        match self.form_request(Method::Post, path, form) {
+            Ok(req) => self.execute::<T>(req),
+            Err(e) => err(e),
+        }
+    }
 
-        let mut params_buffer = Vec::new();
-        let qs_ser = &mut serde_qs::Serializer::new(&mut params_buffer);
-        if let Err(qs_ser_err) = serde_path_to_error::serialize(&form, qs_ser) {
-            return err(StripeError::QueryStringSerialize(qs_ser_err));
+    /// Make an http request to an arbitrary path, deserializing the response into `T`.
+    ///
+    /// This is an escape hatch for endpoints which do not (yet) have a method in
+    /// this crate, such as beta apis. The path is relative to the api root, for
+    /// example `customers/cus_123`. The params are sent as url query parameters for
+    /// `GET` and `DELETE` requests and as an urlencoded body otherwise, and the
+    /// request is sent with the same headers and strategy as any other request.
+    ///
+    /// ```no_run
+    /// # use serde::{Deserialize, Serialize};
+    /// # use stripe::{Client, Method};
+    /// # fn run(client: &Client) {
+    /// #[derive(Serialize)]
+    /// struct CreateFeature<'a> {
+    ///     name: &'a str,
+    ///     lookup_key: &'a str,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Feature {
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// let params = CreateFeature { name: "Super Feature", lookup_key: "super-feature" };
+    /// let feature: stripe::Response<Feature> =
+    ///     client.request(Method::Post, "entitlements/features", params);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// If the params are not serialized to an utf8 string.
+    pub fn request<T: DeserializeOwned + Send + 'static, P: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        params: P,
+    ) -> Response<T> {
+        match self.raw_request(method, path, params) {
+            Ok(req) => self.execute::<T>(req),
+            Err(e) => err(e),
         }
+    }
 
-        let body = std::str::from_utf8(params_buffer.as_slice())
-            .expect("Unable to extract string from params_buffer")
-            .to_string();
+    /// Make an http request to an arbitrary path, returning the raw json response.
+    ///
+    /// A `null` value sends no params. See [`Client::request`] for details.
+    ///
+    /// # Panics
+    /// If the params are not serialized to an utf8 string.
+    pub fn request_raw(
+        &self,
+        method: Method,
+        path: &str,
+        params: &serde_json::Value,
+    ) -> Response<serde_json::Value> {
+        match params {
+            serde_json::Value::Null => self.request(method, path, serde_json::Map::new()),
+            params => self.request(method, path, params),
+        }
+    }
 
-        req.set_body(Body::from_string(body));
+    fn execute<T: DeserializeOwned + Send + 'static>(&self, request: Request) -> Response<T> {
+        self.execute_map(request, |value| value)
+    }
 
-        req.insert_header("content-type", "application/x-www-form-urlencoded");
-        self.client.execute::<T>(req, &self.strategy)
+    fn execute_map<T, U>(
+        &self,
+        request: Request,
+        f: impl FnOnce(T) -> U + Send + 'static,
+    ) -> Response<U>
+    where
+        T: DeserializeOwned + Send + 'static,
+        U: Send + 'static,
+    {
+        let config = RequestConfig {
+            strategy: self.strategy().clone(),
+            idempotency_key: self.idempotency_key(&request),
+            timeout: self.options.timeout.or(self.timeout),
+            total_timeout: self.total_timeout,
+            meta: self.meta.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            hooks: self.hooks.clone(),
+            secret: self.secret_provider.clone(),
+            mode: self.mode,
+        };
+
+        #[cfg(feature = "sans-io")]
+        if let Some(slot) = &self.prepared {
+            // the request is authorized by `prepare`, which may wait for the secret provider
+            let mut request = request;
+            config.prepare(&mut request);
+            *slot.lock().unwrap() = Some(request);
+            return err(StripeError::ClientError("the request was prepared, not sent".to_string()));
+        }
+
+        self.client.execute_map::<T, U>(request, config, f)
+    }
+
+    fn strategy(&self) -> &RequestStrategy {
+        self.options.strategy.as_ref().unwrap_or(&self.strategy)
+    }
+
+    fn idempotency_key(&self, request: &Request) -> Option<String> {
+        let key = self
+            .idempotency_key
+            .as_ref()
+            .and_then(|key| key.lock().unwrap_or_else(|e| e.into_inner()).take())
+            .or_else(|| self.strategy().get_key());
+
+        #[cfg(feature = "uuid")]
+        if key.is_none() && self.automatic_idempotency_keys && request.method() == Method::Post {
+            return Some(uuid::Uuid::new_v4().to_string());
+        }
+
+        #[cfg(not(feature = "uuid"))]
+        let _ = request;
+
+        key
     }
 
     fn url(&self, path: &str) -> Url {
@@ -177,18 +691,88 @@ impl Client {
         Ok(url)
     }
 
+    pub(crate) fn raw_request<P: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        params: P,
+    ) -> Result<Request, StripeError> {
+        match method {
+            Method::Get | Method::Delete => {
+                self.url_with_params(path, params).map(|url| self.create_request(method, url))
+            }
+            method => self.form_request(method, path, params),
+        }
+    }
+
+    fn form_request<F: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        form: F,
+    ) -> Result<Request, StripeError> {
+        let url = self.url(path);
+        let mut req = self.create_request(method, url);
+
+        let mut params_buffer = Vec::new();
+        let qs_ser = &mut serde_qs::Serializer::new(&mut params_buffer);
+        serde_path_to_error::serialize(&form, qs_ser).map_err(StripeError::from)?;
+
+        let body = std::str::from_utf8(params_buffer.as_slice())
+            .expect("Unable to extract string from params_buffer")
+            .to_string();
+
+        req.set_body(Body::from_string(body));
+
+        req.insert_header("content-type", "application/x-www-form-urlencoded");
+        Ok(req)
+    }
+
     fn create_request(&self, method: Method, url: Url) -> Request {
         let mut req = Request::new(method, url);
-        req.insert_header("authorization", &format!("Bearer {}", self.secret_key));
+        authorize(&mut req, &self.secret_key);
 
         for (key, value) in self.headers.to_array().iter().filter_map(|(k, v)| v.map(|v| (*k, v))) {
             req.insert_header(key, value);
         }
+        if let Some(id) = &self.options.stripe_account {
+            req.insert_header("Stripe-Account", id.as_str());
+        }
+        if let Some(version) = &self.options.stripe_version {
+            req.insert_header("Stripe-Version", version.as_str());
+        }
 
         req
     }
 }
 
+/// A place for the client to store the request it would have sent, see [`Client::prepare`].
+#[cfg(feature = "sans-io")]
+type PreparedSlot = Arc<std::sync::Mutex<Option<Request>>>;
+
+/// Take the request recorded by an api call, or the error which stopped it from being built.
+#[cfg(feature = "sans-io")]
+fn take_prepared<T>(
+    slot: &PreparedSlot,
+    result: Result<T, StripeError>,
+) -> Result<Request, StripeError> {
+    let request = slot.lock().ok().and_then(|mut request| request.take());
+    match (request, result) {
+        (Some(request), _) => Ok(request),
+        (None, Err(err)) => Err(err),
+        (None, Ok(_)) => {
+            Err(StripeError::ClientError("the api call did not make a request".to_string()))
+        }
+    }
+}
+
+fn take_meta(slot: &ResponseMetaSlot) -> Result<ResponseMeta, StripeError> {
+    slot.lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| StripeError::ClientError("no response was received".to_string()))
+}
+
 #[cfg(test)]
 mod test {
     //! Ensures our user agent matches the format of the other stripe clients.
@@ -196,6 +780,7 @@ mod test {
     //! See: <https://github.com/stripe/stripe-python/blob/3b917dc4cec6a3cccfd46961e05fe7b55c6bee87/stripe/api_requestor.py#L241>
 
     use super::Client;
+    use crate::{ApiVersion, Method, RequestOptions, RequestStrategy};
 
     #[test]
     fn user_agent_base() {
@@ -234,4 +819,78 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn request_options() {
+        let client = Client::new("sk_test_12345").with_strategy(RequestStrategy::Retry(3));
+
+        let options = RequestOptions {
+            stripe_account: Some("acct_123".parse().unwrap()),
+            idempotency_key: Some("key".to_string()),
+            stripe_version: Some(ApiVersion::V2022_11_15),
+            strategy: Some(RequestStrategy::ExponentialBackoff(2)),
+            ..Default::default()
+        };
+        let with_options = client.with_options(options);
+
+        let post =
+            with_options.raw_request(Method::Post, "customers", serde_json::json!({})).unwrap();
+        assert_eq!(post.header("stripe-account").unwrap(), "acct_123");
+        assert_eq!(post.header("stripe-version").unwrap(), ApiVersion::V2022_11_15.as_str());
+        assert!(matches!(with_options.strategy(), RequestStrategy::ExponentialBackoff(2)));
+
+        // options applied later only replace the ones they set
+        let timeout = std::time::Duration::from_secs(5);
+        let layered = with_options
+            .with_options(RequestOptions { timeout: Some(timeout), ..Default::default() });
+        assert!(matches!(layered.strategy(), RequestStrategy::ExponentialBackoff(2)));
+        assert_eq!(layered.options.timeout, Some(timeout));
+
+        // the key is only used by the first request, even when made by a clone
+        assert_eq!(layered.clone().idempotency_key(&post).as_deref(), Some("key"));
+        assert_ne!(with_options.idempotency_key(&post).as_deref(), Some("key"));
+
+        // the original client is left untouched
+        let post = client.raw_request(Method::Post, "customers", serde_json::json!({})).unwrap();
+        assert!(post.header("stripe-account").is_none());
+        assert_ne!(client.idempotency_key(&post).as_deref(), Some("key"));
+    }
+
+    #[test]
+    fn raw_request() {
+        let client = Client::new("sk_test_12345");
+
+        let get =
+            client.raw_request(Method::Get, "features", serde_json::json!({"limit": 3})).unwrap();
+        assert_eq!(get.method(), Method::Get);
+        assert_eq!(get.url().as_str(), "https://api.stripe.com/v1/features?limit=3");
+
+        let mut post = client
+            .raw_request(Method::Post, "/features", serde_json::json!({"name": "feature"}))
+            .unwrap();
+        assert_eq!(post.method(), Method::Post);
+        assert_eq!(post.url().as_str(), "https://api.stripe.com/v1/features");
+        assert_eq!(post.header("authorization").unwrap(), "Bearer sk_test_12345");
+        assert_eq!(async_std::task::block_on(post.body_string()).unwrap(), "name=feature");
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn automatic_idempotency_keys() {
+        let client = Client::new("sk_test_12345");
+        let get = client.raw_request(Method::Get, "customers", serde_json::json!({})).unwrap();
+        let post = client.raw_request(Method::Post, "customers", serde_json::json!({})).unwrap();
+        assert_eq!(client.idempotency_key(&post), None);
+
+        let client = client.with_automatic_idempotency_keys();
+        assert_eq!(client.idempotency_key(&get), None);
+        assert!(client.idempotency_key(&post).is_some());
+        assert_ne!(client.idempotency_key(&post), client.idempotency_key(&post));
+
+        // an explicit key takes precedence
+        let options =
+            RequestOptions { idempotency_key: Some("key".to_string()), ..Default::default() };
+        let client = client.with_options(options);
+        assert_eq!(client.idempotency_key(&post).as_deref(), Some("key"));
+    }
 }
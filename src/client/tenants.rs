@@ -0,0 +1,143 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+use crate::{AccountId, Client, SecretKey};
+
+/// A registry of clients for many tenants, keyed by an id of your choosing.
+///
+/// Every client in the registry is derived from a single base client with
+/// [`Client::with_secret_key`], so they all share its transport, and with it
+/// its connection pool, however many tenants are registered.
+///
+/// ```no_run
+/// # use stripe::{Client, TenantRegistry};
+/// let tenants = TenantRegistry::new(Client::new("sk_test_platform"));
+/// tenants.insert("acme", "sk_test_acme", None);
+/// tenants.insert("globex", "sk_test_platform", Some("acct_123".parse().unwrap()));
+///
+/// let client = tenants.get("acme").expect("acme is registered");
+/// ```
+pub struct TenantRegistry<K> {
+    base: Client,
+    tenants: RwLock<HashMap<K, Client>>,
+}
+
+impl<K: Eq + Hash> TenantRegistry<K> {
+    /// Create an empty registry, deriving the client of each tenant from the given one.
+    pub fn new(base: Client) -> Self {
+        Self { base, tenants: RwLock::default() }
+    }
+
+    /// Register a tenant using the given secret key, optionally making its
+    /// requests on behalf of a connected account.
+    ///
+    /// This replaces any client previously registered for the tenant, and
+    /// returns the new one.
+    pub fn insert(
+        &self,
+        tenant: K,
+        secret_key: impl Into<SecretKey>,
+        stripe_account: Option<AccountId>,
+    ) -> Client {
+        let mut client = self.base.with_secret_key(secret_key);
+        if let Some(account) = stripe_account {
+            client = client.with_stripe_account(account);
+        }
+        self.write().insert(tenant, client.clone());
+        client
+    }
+
+    /// Get the client of a tenant, if it is registered.
+    pub fn get<Q>(&self, tenant: &Q) -> Option<Client>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.read().get(tenant).cloned()
+    }
+
+    /// Remove a tenant from the registry, returning its client if it was registered.
+    pub fn remove<Q>(&self, tenant: &Q) -> Option<Client>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.write().remove(tenant)
+    }
+
+    /// The number of registered tenants.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Whether no tenant is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // the map is never left in an inconsistent state, so a poisoned lock can be recovered
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<K, Client>> {
+        self.tenants.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<K, Client>> {
+        self.tenants.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::TenantRegistry;
+    use crate::{Client, Transport, TransportFuture};
+
+    /// Records the credentials of every request it is sent.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<(String, Option<String>)>>>);
+
+    impl Transport for Recorder {
+        fn send(&self, request: http_types::Request) -> TransportFuture<'_> {
+            let header = |name| request.header(name).map(|h| h.last().to_string());
+            self.0
+                .lock()
+                .unwrap()
+                .push((header("authorization").unwrap(), header("stripe-account")));
+            Box::pin(async {
+                let mut response = http_types::Response::new(200);
+                response.set_body("{}");
+                Ok(response)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn tenants() {
+        let recorder = Recorder::default();
+        let tenants =
+            TenantRegistry::new(Client::new("sk_test_platform").with_transport(recorder.clone()));
+        tenants.insert("acme".to_string(), "sk_test_acme", None);
+        tenants.insert("globex".to_string(), "sk_test_platform", Some("acct_123".parse().unwrap()));
+        assert_eq!(tenants.len(), 2);
+
+        for tenant in ["acme", "globex"] {
+            let client = tenants.get(tenant).unwrap();
+            client.get::<serde_json::Value>("balance").await.unwrap();
+        }
+
+        // both tenants sent their requests over the transport of the base client
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                ("Bearer sk_test_acme".to_string(), None),
+                ("Bearer sk_test_platform".to_string(), Some("acct_123".to_string())),
+            ]
+        );
+
+        assert!(tenants.remove("acme").is_some());
+        assert!(tenants.get("acme").is_none());
+        assert_eq!(tenants.len(), 1);
+    }
+}
@@ -0,0 +1,352 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "async-std")]
+use async_std::{future::timeout, task::sleep};
+use bytes::Bytes;
+use http_types::{Request, StatusCode};
+use serde::de::DeserializeOwned;
+#[cfg(not(feature = "async-std"))]
+use tokio::time::{sleep, timeout};
+use zeroize::Zeroizing;
+
+use crate::client::hooks::Hooks;
+use crate::client::instrument::Instrumentation;
+use crate::client::mode::Mode;
+use crate::client::rate_limit::RateLimiter;
+use crate::client::request_strategy::{Outcome, RequestStrategy};
+use crate::client::response_meta::{ResponseMeta, ResponseMetaSlot};
+use crate::client::retry_policy::Attempt;
+use crate::client::secret::{CachedSecret, SecretKey};
+use crate::error::{ErrorCode, ErrorResponse, ErrorType, StripeError};
+
+/// The future returned by [`Transport::send`].
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<http_types::Response, StripeError>> + Send + 'a>>;
+
+/// The HTTP layer used by the [`Client`](crate::Client) to talk to Stripe.
+///
+/// A transport is handed a fully formed request (url, headers and body) and
+/// should perform a single attempt at sending it, returning the raw response
+/// regardless of its status. Retries, idempotency keys and the decoding of
+/// stripe errors are handled by the client on top of this trait, so an
+/// implementation should not attempt any of them itself.
+///
+/// Each runtime provides a default implementation, but you may provide your
+/// own using [`Client::with_transport`](crate::Client::with_transport), for
+/// example to reuse an existing http client, to fake responses in tests, or
+/// to wrap the default transport with some instrumentation.
+pub trait Transport: Send + Sync {
+    /// Send a request, returning the response as received from the server.
+    fn send(&self, request: Request) -> TransportFuture<'_>;
+
+    /// Send a request whose body has already been read, which is how the client
+    /// sends every attempt.
+    ///
+    /// The body of `request` is ignored in favour of `body`, which is cheap to
+    /// clone, so that an implementation which builds its own request from them
+    /// does not copy the request or its body on each attempt. The default
+    /// implementation copies them into a new request passed to [`send`](Self::send).
+    fn send_buffered(&self, request: &Request, body: Bytes) -> TransportFuture<'_> {
+        let mut request = request.clone();
+        request.set_body(body.to_vec());
+        self.send(request)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        (**self).send(request)
+    }
+
+    fn send_buffered(&self, request: &Request, body: Bytes) -> TransportFuture<'_> {
+        (**self).send_buffered(request, body)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        (**self).send(request)
+    }
+
+    fn send_buffered(&self, request: &Request, body: Bytes) -> TransportFuture<'_> {
+        (**self).send_buffered(request, body)
+    }
+}
+
+/// The settings used by the client when sending a single request.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RequestConfig {
+    pub strategy: RequestStrategy,
+    /// An idempotency key which takes precedence over the one from the strategy.
+    pub idempotency_key: Option<String>,
+    /// The maximum time a single attempt may take, including reading the body.
+    pub timeout: Option<Duration>,
+    /// The maximum time the request may take, including all of its retries.
+    pub total_timeout: Option<Duration>,
+    /// Where to store the metadata of the last response, if requested.
+    pub meta: Option<ResponseMetaSlot>,
+    /// The limiter to reserve capacity from before each attempt.
+    pub rate_limiter: Option<RateLimiter>,
+    /// The hooks to call around the request.
+    pub hooks: Arc<Hooks>,
+    /// The provider of the secret key to authorize the request with, if not a fixed one.
+    pub secret: Option<Arc<CachedSecret>>,
+    /// The mode the key and the objects received must be in, if it is checked.
+    pub mode: Option<Mode>,
+}
+
+impl RequestConfig {
+    /// Set the idempotency key header on a request which is about to be sent,
+    /// and let the hooks modify it.
+    pub fn prepare(&self, request: &mut Request) {
+        if let Some(key) = self.idempotency_key.clone().or_else(|| self.strategy.get_key()) {
+            request.insert_header("Idempotency-Key", key);
+        }
+        self.hooks.before_request(request);
+    }
+
+    /// Deserialize the body fetched for a request, and let the hooks know if it failed.
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        body: Result<Vec<u8>, StripeError>,
+    ) -> Result<T, StripeError> {
+        let result = body.and_then(|bytes| {
+            if let Some(mode) = self.mode.filter(|_| cfg!(debug_assertions)) {
+                mode.check_livemode(&bytes)?;
+            }
+            decode_body(&bytes)
+        });
+
+        if let Err(error) = &result {
+            self.hooks.on_error(error);
+        }
+        result
+    }
+}
+
+impl From<&RequestStrategy> for RequestConfig {
+    fn from(strategy: &RequestStrategy) -> Self {
+        Self { strategy: strategy.clone(), ..Default::default() }
+    }
+}
+
+/// Send a request over the given transport, retrying according to the strategy,
+/// and deserialize the response body.
+pub(crate) async fn execute<T: DeserializeOwned>(
+    transport: &dyn Transport,
+    request: Request,
+    config: &RequestConfig,
+) -> Result<T, StripeError> {
+    let body = fetch(transport, request, config).await;
+    config.decode(body)
+}
+
+/// Send a request over the given transport, retrying according to the strategy,
+/// and return the response body without deserializing it.
+///
+/// The body must be passed to [`RequestConfig::decode`] afterwards.
+pub(crate) async fn fetch(
+    transport: &dyn Transport,
+    mut request: Request,
+    config: &RequestConfig,
+) -> Result<Vec<u8>, StripeError> {
+    config.prepare(&mut request);
+    let instrumentation = Instrumentation::new(&request);
+    let started = Instant::now();
+    let sending = send(transport, request, config, &instrumentation);
+    let sending = async {
+        // the request is only recorded once it completes, so that requests which
+        // run out of time are recorded as well
+        let result = match config.total_timeout {
+            Some(duration) => timeout(duration, sending).await.unwrap_or(Err(StripeError::Timeout)),
+            None => sending.await,
+        };
+        instrumentation.finish(started.elapsed(), result.as_ref().map(|(status, _)| *status));
+        result.map(|(_, bytes)| bytes)
+    };
+    instrumentation.instrument(sending).await
+}
+
+/// Authorize a request with the key from the secret provider, if there is one.
+///
+/// If stripe rejects a cached key, a new one is fetched and the request is sent
+/// once more, so that rotated keys are picked up without failing any request.
+async fn send(
+    transport: &dyn Transport,
+    mut request: Request,
+    config: &RequestConfig,
+    instrumentation: &Instrumentation,
+) -> Result<(StatusCode, Vec<u8>), StripeError> {
+    // the body is read once, and shared by every attempt
+    let body = Bytes::from(request.body_bytes().await?);
+
+    let Some(secret) = &config.secret else {
+        check_mode(&request, config.mode)?;
+        return send_inner(transport, &request, body, config, instrumentation).await;
+    };
+
+    let key = secret.get().await?;
+    authorize(&mut request, &key);
+    check_mode(&request, config.mode)?;
+
+    match send_inner(transport, &request, body.clone(), config, instrumentation).await {
+        Err(StripeError::Stripe(error))
+            if error.error_type == ErrorType::Authentication
+                || error.code == Some(ErrorCode::ApiKeyExpired) =>
+        {
+            authorize(&mut request, &secret.refresh(&key).await?);
+            check_mode(&request, config.mode)?;
+            send_inner(transport, &request, body, config, instrumentation).await
+        }
+        result => result,
+    }
+}
+
+/// Set the authorization header of a request to the given key.
+///
+/// The header value is formatted in a buffer which is zeroized once it has been
+/// copied into the request. The copy held by the request, and any made by the
+/// transport, are not zeroized, so the key remains in memory until they are freed.
+pub(crate) fn authorize(request: &mut Request, key: &SecretKey) {
+    let value = Zeroizing::new(format!("Bearer {}", key.expose()));
+    request.insert_header("authorization", value.as_str());
+}
+
+/// Fail if the request is authorized with a key for another mode than the expected one.
+pub(crate) fn check_mode(request: &Request, mode: Option<Mode>) -> Result<(), StripeError> {
+    let Some(mode) = mode else {
+        return Ok(());
+    };
+    let authorization = request.header("authorization").map(|h| h.last().as_str());
+    mode.check_key(authorization.and_then(|a| a.strip_prefix("Bearer ")).unwrap_or_default())
+}
+
+/// Decode the body of a successful response.
+pub(crate) fn decode_body<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StripeError> {
+    let json_deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+    serde_path_to_error::deserialize(json_deserializer).map_err(StripeError::from)
+}
+
+/// Decode the error stripe sent in the body of an unsuccessful response.
+pub(crate) fn decode_error(status: u16, request_id: Option<String>, bytes: &[u8]) -> StripeError {
+    let json_deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+    serde_path_to_error::deserialize(json_deserializer)
+        .map(|mut e: ErrorResponse| {
+            e.error.http_status = status;
+            e.error.request_id = request_id;
+            StripeError::from(e.error)
+        })
+        .unwrap_or_else(StripeError::from)
+}
+
+async fn send_inner(
+    transport: &dyn Transport,
+    request: &Request,
+    body: Bytes,
+    config: &RequestConfig,
+    instrumentation: &Instrumentation,
+) -> Result<(StatusCode, Vec<u8>), StripeError> {
+    let strategy = &config.strategy;
+    let mut tries = 0;
+    let mut last_status: Option<StatusCode> = None;
+    let mut last_retry_header: Option<bool> = None;
+    let mut last_retry_after: Option<Duration> = None;
+    let started = Instant::now();
+
+    // if we have no last error, then the strategy is invalid
+    let mut last_error = StripeError::ClientError("Invalid strategy".to_string());
+
+    loop {
+        let attempt = Attempt {
+            count: tries,
+            status: last_status,
+            error: (tries > 0).then_some(&last_error),
+            stripe_should_retry: last_retry_header,
+            retry_after: last_retry_after,
+            elapsed: started.elapsed(),
+        };
+
+        return match strategy.test_attempt(&attempt) {
+            Outcome::Stop => Err(last_error),
+            Outcome::Continue(duration) => {
+                if tries > 0 {
+                    instrumentation.retry(tries + 1, duration, &last_error);
+                }
+
+                if let Some(duration) = duration {
+                    sleep(duration).await;
+                }
+
+                if let Some(wait) = config.rate_limiter.as_ref().and_then(|l| l.reserve(request)) {
+                    instrumentation.rate_limited(wait);
+                    sleep(wait).await;
+                }
+
+                instrumentation.attempt(tries + 1);
+
+                let sent = Instant::now();
+                let attempt = send_attempt(transport, request, body.clone(), config.timeout);
+                let (meta, bytes) = match attempt.await {
+                    Ok(response) => response,
+                    Err(err) => {
+                        last_error = err;
+                        last_status = None;
+                        last_retry_header = None;
+                        last_retry_after = None;
+                        tries += 1;
+                        continue;
+                    }
+                };
+
+                let status = meta.status;
+                let retry = meta.should_retry();
+                let retry_after = meta.retry_after();
+                let request_id = meta.request_id().map(str::to_string);
+                instrumentation.response(status, request_id.as_deref());
+                config.hooks.after_response(&meta, sent.elapsed());
+
+                if let Some(slot) = &config.meta {
+                    *slot.lock().unwrap() = Some(meta);
+                }
+
+                if !status.is_success() {
+                    tries += 1;
+                    last_error = decode_error(status.into(), request_id, &bytes);
+                    last_status = Some(status);
+                    last_retry_header = retry;
+                    last_retry_after = retry_after;
+                    continue;
+                }
+
+                Ok((status, bytes))
+            }
+        };
+    }
+}
+
+/// Perform a single attempt at sending the request, reading the metadata
+/// and the body of the response.
+///
+/// A timeout is reported as a [`StripeError::Timeout`] so that it can be
+/// retried like any other network failure.
+async fn send_attempt(
+    transport: &dyn Transport,
+    request: &Request,
+    body: Bytes,
+    duration: Option<Duration>,
+) -> Result<(ResponseMeta, Vec<u8>), StripeError> {
+    let attempt = async {
+        let mut response = transport.send_buffered(request, body).await?;
+        let meta = ResponseMeta::new(&response);
+        let bytes = response.body_bytes().await?;
+        Ok((meta, bytes))
+    };
+
+    match duration {
+        Some(duration) => timeout(duration, attempt).await.map_err(|_| StripeError::Timeout)?,
+        None => attempt.await,
+    }
+}
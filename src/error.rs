@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::params::to_snakecase;
+use crate::Mode;
 
 /// An error encountered when communicating with the Stripe API.
 #[derive(Debug, Error)]
@@ -20,6 +21,8 @@ pub enum StripeError {
     ClientError(String),
     #[error("timeout communicating with stripe")]
     Timeout,
+    #[error("invalid client configuration: {0}")]
+    Config(String),
 }
 
 #[cfg(feature = "hyper")]
@@ -112,6 +115,7 @@ pub enum ErrorCode {
     InvoiceNotEditable,
     InvoiceUpcomingNone,
     LivemodeMismatch,
+    LockTimeout,
     Missing,
     OrderCreationFailed,
     OrderRequiredSettings,
@@ -187,6 +191,12 @@ pub struct RequestError {
 
     /// The ID of the failed charge, if applicable.
     pub charge: Option<String>,
+
+    /// The id of the request, from the `Request-Id` header in the response.
+    ///
+    /// Provide this when contacting stripe support about the error.
+    #[serde(skip_deserializing)]
+    pub request_id: Option<String>,
 }
 
 /// The structure of the json body when an error is included in
@@ -209,4 +219,15 @@ pub enum WebhookError {
     BadTimestamp(i64),
     #[error("error parsing event object")]
     BadParse(#[from] serde_json::Error),
+    #[error("event was not sent in {0} mode")]
+    BadMode(Mode),
+    #[cfg(feature = "events")]
+    #[error("unexpected event type {0}")]
+    BadEventType(crate::resources::EventType),
+    #[error("authorization is not pending")]
+    NotPending,
+    #[error("amount {0} cannot be approved")]
+    BadAmount(i64),
+    #[error("the amount of the authorization cannot be changed")]
+    AmountNotControllable,
 }
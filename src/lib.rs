@@ -49,8 +49,12 @@
 //!                                            it up to n times with exponential backoff. The idempotency key is
 //!                                            generated automatically and is stable across retries.
 //!
-//! > Want to implement your own? If it is a common strategy, please consider opening a PR to add it to the library.
-//! > Otherwise, we are open to turning this into an open trait so that you can implement your own strategy.
+//! - [`RequestStrategy::Custom`]: Make a request to the Stripe API and, if the request fails, ask the provided
+//!                                [`RetryPolicy`] whether and when to retry it. The idempotency key is generated
+//!                                automatically and is stable across retries.
+//!
+//! > Want to implement your own? Implement [`RetryPolicy`], or compose the provided policies such as
+//! > [`JitteredBackoff`] with [`RetryPolicy::only_retryable_errors`] and [`RetryPolicy::with_deadline`].
 
 #![allow(clippy::large_enum_variant)]
 #![warn(clippy::missing_panics_doc)]
@@ -84,3 +88,4 @@ pub use crate::params::{
     Timestamp,
 };
 pub use crate::resources::*;
+pub use http_types::Method;
@@ -418,9 +418,8 @@ where
                     p
                 };
 
-                let page = client.get_query(&path, &params_next);
-
-                ListPaginator::create_paginator(page, params_next)
+                let params = params_next.clone();
+                client.get_query_map(&path, &params_next, |page| ListPaginator { page, params })
             } else {
                 err(StripeError::UnsupportedVersion)
             }
@@ -431,19 +430,6 @@ where
             })
         }
     }
-
-    /// Pin a new future which maps the result inside the page future into
-    /// a ListPaginator
-    #[cfg(feature = "async")]
-    fn create_paginator(page: Response<T>, params: P) -> Response<Self> {
-        use futures_util::FutureExt;
-        Box::pin(page.map(|page| page.map(|page| ListPaginator { page, params })))
-    }
-
-    #[cfg(feature = "blocking")]
-    fn create_paginator(page: Response<T>, params: P) -> Response<Self> {
-        page.map(|page| ListPaginator { page, params })
-    }
 }
 
 pub type CurrencyMap<V> = HashMap<Currency, V>;
@@ -80,6 +80,8 @@ mod fraud {
 #[cfg(feature = "issuing")]
 mod issuing {
     pub mod issuing_authorization_ext;
+    #[cfg(feature = "events")]
+    pub mod issuing_authorization_request;
     pub mod issuing_card_ext;
     pub mod issuing_dispute_ext;
     pub mod issuing_merchant_data;
@@ -315,6 +317,9 @@ pub use {
     },
 };
 
+#[cfg(all(feature = "issuing", feature = "events"))]
+pub use issuing::issuing_authorization_request::*;
+
 #[rustfmt::skip]
 #[cfg(feature = "orders")]
 pub use {
@@ -4,8 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::ids::IssuingAuthorizationId;
-use crate::params::{Expandable, Metadata, Object, Timestamp};
+use crate::client::{Client, Response};
+use crate::ids::{IssuingAuthorizationId, IssuingCardId, IssuingCardholderId};
+use crate::params::{Expand, Expandable, List, Metadata, Object, Paginable, RangeQuery, Timestamp};
 use crate::resources::{
     BalanceTransaction, Currency, IssuingAuthorizationAmountDetails, IssuingAuthorizationCheck,
     IssuingAuthorizationMethod, IssuingAuthorizationReason, IssuingCard, IssuingCardholder,
@@ -120,6 +121,63 @@ pub struct IssuingAuthorization {
     pub wallet: Option<String>,
 }
 
+impl IssuingAuthorization {
+    /// Returns a list of Issuing `Authorization` objects.
+    ///
+    /// The objects are sorted in descending order by creation date, with the most recently created object appearing first.
+    pub fn list(
+        client: &Client,
+        params: &ListIssuingAuthorizations<'_>,
+    ) -> Response<List<IssuingAuthorization>> {
+        client.get_query("/issuing/authorizations", params)
+    }
+
+    /// Retrieves an Issuing `Authorization` object.
+    pub fn retrieve(
+        client: &Client,
+        id: &IssuingAuthorizationId,
+        expand: &[&str],
+    ) -> Response<IssuingAuthorization> {
+        client.get_query(&format!("/issuing/authorizations/{}", id), Expand { expand })
+    }
+
+    /// Updates the specified Issuing `Authorization` object by setting the values of the parameters passed.
+    ///
+    /// Any parameters not provided will be left unchanged.
+    pub fn update(
+        client: &Client,
+        id: &IssuingAuthorizationId,
+        params: UpdateIssuingAuthorization<'_>,
+    ) -> Response<IssuingAuthorization> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/authorizations/{}", id), &params)
+    }
+
+    /// Approves a pending Issuing `Authorization` object.
+    ///
+    /// This request should be made within the timeout window of the [real-time authorization](https://stripe.com/docs/issuing/controls/real-time-authorizations) flow.
+    pub fn approve(
+        client: &Client,
+        id: &IssuingAuthorizationId,
+        params: ApproveIssuingAuthorization<'_>,
+    ) -> Response<IssuingAuthorization> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/authorizations/{}/approve", id), &params)
+    }
+
+    /// Declines a pending Issuing `Authorization` object.
+    ///
+    /// This request should be made within the timeout window of the [real-time authorization](https://stripe.com/docs/issuing/controls/real-time-authorizations) flow.
+    pub fn decline(
+        client: &Client,
+        id: &IssuingAuthorizationId,
+        params: DeclineIssuingAuthorization<'_>,
+    ) -> Response<IssuingAuthorization> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/authorizations/{}/decline", id), &params)
+    }
+}
+
 impl Object for IssuingAuthorization {
     type Id = IssuingAuthorizationId;
     fn id(&self) -> Self::Id {
@@ -289,6 +347,150 @@ pub struct IssuingAuthorizationThreeDSecure {
     pub result: IssuingAuthorizationThreeDSecureResult,
 }
 
+/// The parameters for `IssuingAuthorization::list`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ListIssuingAuthorizations<'a> {
+    /// Only return authorizations that belong to the given card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<IssuingCardId>,
+
+    /// Only return authorizations that belong to the given cardholder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cardholder: Option<IssuingCardholderId>,
+
+    /// Only return authorizations that were created during the given date interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<RangeQuery<Timestamp>>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `ending_before` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, starting with `obj_bar`, your subsequent call can include `ending_before=obj_bar` in order to fetch the previous page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<IssuingAuthorizationId>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// A limit on the number of objects to be returned.
+    ///
+    /// Limit can range between 1 and 100, and the default is 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `starting_after` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, ending with `obj_foo`, your subsequent call can include `starting_after=obj_foo` in order to fetch the next page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<IssuingAuthorizationId>,
+
+    /// Only return authorizations with the given status.
+    ///
+    /// One of `pending`, `closed`, or `reversed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingAuthorizationStatus>,
+}
+
+impl<'a> ListIssuingAuthorizations<'a> {
+    pub fn new() -> Self {
+        ListIssuingAuthorizations {
+            card: Default::default(),
+            cardholder: Default::default(),
+            created: Default::default(),
+            ending_before: Default::default(),
+            expand: Default::default(),
+            limit: Default::default(),
+            starting_after: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl Paginable for ListIssuingAuthorizations<'_> {
+    type O = IssuingAuthorization;
+    fn set_last(&mut self, item: Self::O) {
+        self.starting_after = Some(item.id());
+    }
+}
+
+/// The parameters for `IssuingAuthorization::update`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateIssuingAuthorization<'a> {
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<'a> UpdateIssuingAuthorization<'a> {
+    pub fn new() -> Self {
+        UpdateIssuingAuthorization { expand: Default::default(), metadata: Default::default() }
+    }
+}
+
+/// The parameters for `IssuingAuthorization::approve`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ApproveIssuingAuthorization<'a> {
+    /// If the authorization's `pending_request.is_amount_controllable` property is `true`, you may provide this value to control how much to hold for the authorization.
+    ///
+    /// Must be positive (use [`decline`](IssuingAuthorization::decline) to decline an authorization request).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<'a> ApproveIssuingAuthorization<'a> {
+    pub fn new() -> Self {
+        ApproveIssuingAuthorization {
+            amount: Default::default(),
+            expand: Default::default(),
+            metadata: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `IssuingAuthorization::decline`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct DeclineIssuingAuthorization<'a> {
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<'a> DeclineIssuingAuthorization<'a> {
+    pub fn new() -> Self {
+        DeclineIssuingAuthorization { expand: Default::default(), metadata: Default::default() }
+    }
+}
+
 /// An enum representing the possible values of an `IssuingAuthorizationAuthenticationExemption`'s `claimed_by` field.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -4,8 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::ids::IssuingCardId;
-use crate::params::{Expandable, Metadata, Object, Timestamp};
+use crate::client::{Client, Response};
+use crate::ids::{IssuingCardId, IssuingCardholderId};
+use crate::params::{Expand, Expandable, List, Metadata, Object, Paginable, RangeQuery, Timestamp};
 use crate::resources::{
     Address, CardBrand, Currency, IssuingCardShippingStatus, IssuingCardShippingType,
     IssuingCardType, IssuingCardholder, MerchantCategory,
@@ -100,6 +101,38 @@ pub struct IssuingCard {
     pub wallets: Option<IssuingCardWallets>,
 }
 
+impl IssuingCard {
+    /// Returns a list of Issuing `Card` objects.
+    ///
+    /// The objects are sorted in descending order by creation date, with the most recently created object appearing first.
+    pub fn list(client: &Client, params: &ListIssuingCards<'_>) -> Response<List<IssuingCard>> {
+        client.get_query("/issuing/cards", params)
+    }
+
+    /// Creates an Issuing `Card` object.
+    pub fn create(client: &Client, params: CreateIssuingCard<'_>) -> Response<IssuingCard> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form("/issuing/cards", &params)
+    }
+
+    /// Retrieves an Issuing `Card` object.
+    pub fn retrieve(client: &Client, id: &IssuingCardId, expand: &[&str]) -> Response<IssuingCard> {
+        client.get_query(&format!("/issuing/cards/{}", id), Expand { expand })
+    }
+
+    /// Updates the specified Issuing `Card` object by setting the values of the parameters passed.
+    ///
+    /// Any parameters not provided will be left unchanged.
+    pub fn update(
+        client: &Client,
+        id: &IssuingCardId,
+        params: UpdateIssuingCard<'_>,
+    ) -> Response<IssuingCard> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/cards/{}", id), &params)
+    }
+}
+
 impl Object for IssuingCard {
     type Id = IssuingCardId;
     fn id(&self) -> Self::Id {
@@ -231,6 +264,260 @@ pub struct IssuingCardGooglePay {
     pub ineligible_reason: Option<IssuingCardGooglePayIneligibleReason>,
 }
 
+/// The parameters for `IssuingCard::create`.
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateIssuingCard<'a> {
+    /// The [Cardholder](https://stripe.com/docs/api#issuing_cardholder_object) object with which the card will be associated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cardholder: Option<IssuingCardholderId>,
+
+    /// The currency for the card.
+    pub currency: Currency,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub financial_account: Option<&'a str>,
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// The card this is meant to be a replacement for (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_for: Option<IssuingCardId>,
+
+    /// If `replacement_for` is specified, this should indicate why that card is being replaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_reason: Option<IssuingCardReplacementReason>,
+
+    /// The address where the card will be shipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<CreateIssuingCardShipping<'a>>,
+
+    /// Rules that control spending for this card.
+    ///
+    /// Refer to our [documentation](https://stripe.com/docs/issuing/controls/spending-controls) for more details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_controls: Option<IssuingCardSpendingControls>,
+
+    /// Whether authorizations can be approved on this card.
+    ///
+    /// May be blocked from activating cards depending on past-due Cardholder requirements.
+    /// Defaults to `inactive`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingCardStatus>,
+
+    /// The type of card to issue.
+    ///
+    /// Possible values are `physical` or `virtual`.
+    #[serde(rename = "type")]
+    pub type_: IssuingCardType,
+}
+
+impl<'a> CreateIssuingCard<'a> {
+    pub fn new(currency: Currency, type_: IssuingCardType) -> Self {
+        CreateIssuingCard {
+            cardholder: Default::default(),
+            currency,
+            expand: Default::default(),
+            financial_account: Default::default(),
+            metadata: Default::default(),
+            replacement_for: Default::default(),
+            replacement_reason: Default::default(),
+            shipping: Default::default(),
+            spending_controls: Default::default(),
+            status: Default::default(),
+            type_,
+        }
+    }
+}
+
+/// The parameters for `IssuingCard::list`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ListIssuingCards<'a> {
+    /// Only return cards belonging to the Cardholder with the provided ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cardholder: Option<IssuingCardholderId>,
+
+    /// Only return cards that were issued during the given date interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<RangeQuery<Timestamp>>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `ending_before` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, starting with `obj_bar`, your subsequent call can include `ending_before=obj_bar` in order to fetch the previous page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<IssuingCardId>,
+
+    /// Only return cards that have the given expiration month.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp_month: Option<i64>,
+
+    /// Only return cards that have the given expiration year.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp_year: Option<i64>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Only return cards that have the given last four digits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last4: Option<&'a str>,
+
+    /// A limit on the number of objects to be returned.
+    ///
+    /// Limit can range between 1 and 100, and the default is 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `starting_after` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, ending with `obj_foo`, your subsequent call can include `starting_after=obj_foo` in order to fetch the next page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<IssuingCardId>,
+
+    /// Only return cards that have the given status.
+    ///
+    /// One of `active`, `inactive`, or `canceled`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingCardStatus>,
+
+    /// Only return cards that have the given type.
+    ///
+    /// One of `virtual` or `physical`.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<IssuingCardType>,
+}
+
+impl<'a> ListIssuingCards<'a> {
+    pub fn new() -> Self {
+        ListIssuingCards {
+            cardholder: Default::default(),
+            created: Default::default(),
+            ending_before: Default::default(),
+            exp_month: Default::default(),
+            exp_year: Default::default(),
+            expand: Default::default(),
+            last4: Default::default(),
+            limit: Default::default(),
+            starting_after: Default::default(),
+            status: Default::default(),
+            type_: Default::default(),
+        }
+    }
+}
+
+impl Paginable for ListIssuingCards<'_> {
+    type O = IssuingCard;
+    fn set_last(&mut self, item: Self::O) {
+        self.starting_after = Some(item.id());
+    }
+}
+
+/// The parameters for `IssuingCard::update`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateIssuingCard<'a> {
+    /// Reason why the `status` of this card is `canceled`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancellation_reason: Option<IssuingCardCancellationReason>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// Rules that control spending for this card.
+    ///
+    /// Refer to our [documentation](https://stripe.com/docs/issuing/controls/spending-controls) for more details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_controls: Option<IssuingCardSpendingControls>,
+
+    /// Dictates whether authorizations can be approved on this card.
+    ///
+    /// May be blocked from activating cards depending on past-due Cardholder requirements.
+    /// If this card is being canceled because it was lost or stolen, this information should be provided as `cancellation_reason`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingCardStatus>,
+}
+
+impl<'a> UpdateIssuingCard<'a> {
+    pub fn new() -> Self {
+        UpdateIssuingCard {
+            cancellation_reason: Default::default(),
+            expand: Default::default(),
+            metadata: Default::default(),
+            spending_controls: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateIssuingCardShipping<'a> {
+    /// The address that the card is shipped to.
+    pub address: Address,
+
+    /// The name printed on the shipping label when shipping the card.
+    pub name: &'a str,
+
+    /// Phone number of the recipient of the shipment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<&'a str>,
+
+    /// Whether a signature is required for card delivery.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_signature: Option<bool>,
+
+    /// Shipment service.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<IssuingCardShippingService>,
+
+    /// Packaging options.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<IssuingCardShippingType>,
+}
+
+/// Rules that control spending for a card, as set when creating or updating it.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IssuingCardSpendingControls {
+    /// Array of strings containing [categories](https://stripe.com/docs/api#issuing_authorization_object-merchant_data-category) of authorizations to allow.
+    ///
+    /// All other categories will be blocked.
+    /// Cannot be set with `blocked_categories`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_categories: Option<Vec<MerchantCategory>>,
+
+    /// Array of strings containing [categories](https://stripe.com/docs/api#issuing_authorization_object-merchant_data-category) of authorizations to decline.
+    ///
+    /// All other categories will be allowed.
+    /// Cannot be set with `allowed_categories`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_categories: Option<Vec<MerchantCategory>>,
+
+    /// Limit spending with amount-based rules that apply across any cards this card replaced (i.e., its `replacement_for` card and _that_ card's `replacement_for` card, up the chain).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_limits: Option<Vec<IssuingCardSpendingLimit>>,
+}
+
 /// An enum representing the possible values of an `IssuingCardApplePay`'s `ineligible_reason` field.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
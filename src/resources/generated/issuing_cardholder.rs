@@ -4,8 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::client::{Client, Response};
 use crate::ids::IssuingCardholderId;
-use crate::params::{Expandable, Metadata, Object, Timestamp};
+use crate::params::{Expand, Expandable, List, Metadata, Object, Paginable, RangeQuery, Timestamp};
 use crate::resources::{Address, Currency, File, MerchantCategory};
 
 /// The resource representing a Stripe "IssuingCardholder".
@@ -73,6 +74,48 @@ pub struct IssuingCardholder {
     pub type_: IssuingCardholderType,
 }
 
+impl IssuingCardholder {
+    /// Returns a list of Issuing `Cardholder` objects.
+    ///
+    /// The objects are sorted in descending order by creation date, with the most recently created object appearing first.
+    pub fn list(
+        client: &Client,
+        params: &ListIssuingCardholders<'_>,
+    ) -> Response<List<IssuingCardholder>> {
+        client.get_query("/issuing/cardholders", params)
+    }
+
+    /// Creates a new Issuing `Cardholder` object that can be issued cards.
+    pub fn create(
+        client: &Client,
+        params: CreateIssuingCardholder<'_>,
+    ) -> Response<IssuingCardholder> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form("/issuing/cardholders", &params)
+    }
+
+    /// Retrieves an Issuing `Cardholder` object.
+    pub fn retrieve(
+        client: &Client,
+        id: &IssuingCardholderId,
+        expand: &[&str],
+    ) -> Response<IssuingCardholder> {
+        client.get_query(&format!("/issuing/cardholders/{}", id), Expand { expand })
+    }
+
+    /// Updates the specified Issuing `Cardholder` object by setting the values of the parameters passed.
+    ///
+    /// Any parameters not provided will be left unchanged.
+    pub fn update(
+        client: &Client,
+        id: &IssuingCardholderId,
+        params: UpdateIssuingCardholder<'_>,
+    ) -> Response<IssuingCardholder> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/cardholders/{}", id), &params)
+    }
+}
+
 impl Object for IssuingCardholder {
     type Id = IssuingCardholderId;
     fn id(&self) -> Self::Id {
@@ -212,6 +255,250 @@ pub struct IssuingCardholderIdDocument {
     pub front: Option<Expandable<File>>,
 }
 
+/// The parameters for `IssuingCardholder::create`.
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateIssuingCardholder<'a> {
+    /// The cardholder's billing address.
+    pub billing: IssuingCardholderAddress,
+
+    /// The cardholder's email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// The cardholder's name.
+    ///
+    /// This will be printed on cards issued to them.
+    /// The maximum length of this field is 24 characters.
+    pub name: &'a str,
+
+    /// The cardholder's phone number.
+    ///
+    /// This will be transformed to [E.164](https://en.wikipedia.org/wiki/E.164) if it is not provided in that format already.
+    /// This is required for all cardholders who will be creating EU cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<&'a str>,
+
+    /// The cardholder’s preferred locales (languages), ordered by preference.
+    ///
+    /// Locales can be `de`, `en`, `es`, `fr`, or `it`.
+    /// This changes the language of the [3D Secure flow](https://stripe.com/docs/issuing/3d-secure) and one-time password messages sent to the cardholder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_locales: Option<Vec<IssuingCardholderPreferredLocales>>,
+
+    /// Rules that control spending across this cardholder's cards.
+    ///
+    /// Refer to our [documentation](https://stripe.com/docs/issuing/controls/spending-controls) for more details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_controls: Option<IssuingCardholderSpendingControls>,
+
+    /// Specifies whether to permit authorizations on this cardholder's cards.
+    ///
+    /// Defaults to `active`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingCardholderStatus>,
+
+    /// One of `individual` or `company`.
+    ///
+    /// See [Choose a cardholder type](https://stripe.com/docs/issuing/other/choose-cardholder) for more details.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<IssuingCardholderType>,
+}
+
+impl<'a> CreateIssuingCardholder<'a> {
+    pub fn new(billing: IssuingCardholderAddress, name: &'a str) -> Self {
+        CreateIssuingCardholder {
+            billing,
+            email: Default::default(),
+            expand: Default::default(),
+            metadata: Default::default(),
+            name,
+            phone_number: Default::default(),
+            preferred_locales: Default::default(),
+            spending_controls: Default::default(),
+            status: Default::default(),
+            type_: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `IssuingCardholder::list`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ListIssuingCardholders<'a> {
+    /// Only return cardholders that were created during the given date interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<RangeQuery<Timestamp>>,
+
+    /// Only return cardholders that have the given email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `ending_before` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, starting with `obj_bar`, your subsequent call can include `ending_before=obj_bar` in order to fetch the previous page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<IssuingCardholderId>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// A limit on the number of objects to be returned.
+    ///
+    /// Limit can range between 1 and 100, and the default is 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Only return cardholders that have the given phone number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<&'a str>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `starting_after` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, ending with `obj_foo`, your subsequent call can include `starting_after=obj_foo` in order to fetch the next page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<IssuingCardholderId>,
+
+    /// Only return cardholders that have the given status.
+    ///
+    /// One of `active`, `inactive`, or `blocked`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingCardholderStatus>,
+
+    /// Only return cardholders that have the given type.
+    ///
+    /// One of `individual` or `company`.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<IssuingCardholderType>,
+}
+
+impl<'a> ListIssuingCardholders<'a> {
+    pub fn new() -> Self {
+        ListIssuingCardholders {
+            created: Default::default(),
+            email: Default::default(),
+            ending_before: Default::default(),
+            expand: Default::default(),
+            limit: Default::default(),
+            phone_number: Default::default(),
+            starting_after: Default::default(),
+            status: Default::default(),
+            type_: Default::default(),
+        }
+    }
+}
+
+impl Paginable for ListIssuingCardholders<'_> {
+    type O = IssuingCardholder;
+    fn set_last(&mut self, item: Self::O) {
+        self.starting_after = Some(item.id());
+    }
+}
+
+/// The parameters for `IssuingCardholder::update`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateIssuingCardholder<'a> {
+    /// The cardholder's billing address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing: Option<IssuingCardholderAddress>,
+
+    /// The cardholder's email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// The cardholder's phone number.
+    ///
+    /// This is required for all cardholders who will be creating EU cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<&'a str>,
+
+    /// The cardholder’s preferred locales (languages), ordered by preference.
+    ///
+    /// Locales can be `de`, `en`, `es`, `fr`, or `it`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_locales: Option<Vec<IssuingCardholderPreferredLocales>>,
+
+    /// Rules that control spending across this cardholder's cards.
+    ///
+    /// Refer to our [documentation](https://stripe.com/docs/issuing/controls/spending-controls) for more details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_controls: Option<IssuingCardholderSpendingControls>,
+
+    /// Specifies whether to permit authorizations on this cardholder's cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingCardholderStatus>,
+}
+
+impl<'a> UpdateIssuingCardholder<'a> {
+    pub fn new() -> Self {
+        UpdateIssuingCardholder {
+            billing: Default::default(),
+            email: Default::default(),
+            expand: Default::default(),
+            metadata: Default::default(),
+            phone_number: Default::default(),
+            preferred_locales: Default::default(),
+            spending_controls: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+/// Rules that control spending across a cardholder's cards, as set when creating or updating it.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IssuingCardholderSpendingControls {
+    /// Array of strings containing [categories](https://stripe.com/docs/api#issuing_authorization_object-merchant_data-category) of authorizations to allow.
+    ///
+    /// All other categories will be blocked.
+    /// Cannot be set with `blocked_categories`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_categories: Option<Vec<MerchantCategory>>,
+
+    /// Array of strings containing [categories](https://stripe.com/docs/api#issuing_authorization_object-merchant_data-category) of authorizations to decline.
+    ///
+    /// All other categories will be allowed.
+    /// Cannot be set with `allowed_categories`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_categories: Option<Vec<MerchantCategory>>,
+
+    /// Limit spending with amount-based rules that apply across this cardholder's cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_limits: Option<Vec<IssuingCardholderSpendingLimit>>,
+
+    /// Currency of amounts within `spending_limits`.
+    ///
+    /// Defaults to your merchant country's currency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_limits_currency: Option<Currency>,
+}
+
 /// An enum representing the possible values of an `IssuingCardholder`'s `preferred_locales` field.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
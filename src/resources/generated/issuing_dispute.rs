@@ -4,8 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::ids::IssuingDisputeId;
-use crate::params::{Expandable, Metadata, Object, Timestamp};
+use crate::client::{Client, Response};
+use crate::ids::{FileId, IssuingDisputeId, IssuingTransactionId};
+use crate::params::{Expand, Expandable, List, Metadata, Object, Paginable, RangeQuery, Timestamp};
 use crate::resources::{
     BalanceTransaction, Currency, File, IssuingDisputeStatus, IssuingTransaction,
 };
@@ -55,6 +56,61 @@ pub struct IssuingDispute {
     pub treasury: Option<IssuingDisputeTreasury>,
 }
 
+impl IssuingDispute {
+    /// Returns a list of Issuing `Dispute` objects.
+    ///
+    /// The objects are sorted in descending order by creation date, with the most recently created object appearing first.
+    pub fn list(
+        client: &Client,
+        params: &ListIssuingDisputes<'_>,
+    ) -> Response<List<IssuingDispute>> {
+        client.get_query("/issuing/disputes", params)
+    }
+
+    /// Creates an Issuing `Dispute` object.
+    ///
+    /// Individual pieces of evidence within the `evidence` object are optional at this point.
+    /// Stripe only validates that required evidence is present during submission.
+    pub fn create(client: &Client, params: CreateIssuingDispute<'_>) -> Response<IssuingDispute> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form("/issuing/disputes", &params)
+    }
+
+    /// Retrieves an Issuing `Dispute` object.
+    pub fn retrieve(
+        client: &Client,
+        id: &IssuingDisputeId,
+        expand: &[&str],
+    ) -> Response<IssuingDispute> {
+        client.get_query(&format!("/issuing/disputes/{}", id), Expand { expand })
+    }
+
+    /// Updates the specified Issuing `Dispute` object by setting the values of the parameters passed.
+    ///
+    /// Any parameters not provided will be left unchanged.
+    /// Properties on the `evidence` object can be unset by passing in an empty string.
+    pub fn update(
+        client: &Client,
+        id: &IssuingDisputeId,
+        params: UpdateIssuingDispute<'_>,
+    ) -> Response<IssuingDispute> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/disputes/{}", id), &params)
+    }
+
+    /// Submits an Issuing `Dispute` to the card network.
+    ///
+    /// Stripe validates that all evidence fields required for the dispute’s reason are present.
+    pub fn submit(
+        client: &Client,
+        id: &IssuingDisputeId,
+        params: SubmitIssuingDispute<'_>,
+    ) -> Response<IssuingDispute> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/disputes/{}/submit", id), &params)
+    }
+}
+
 impl Object for IssuingDispute {
     type Id = IssuingDisputeId;
     fn id(&self) -> Self::Id {
@@ -240,6 +296,219 @@ pub struct IssuingDisputeTreasury {
     pub received_debit: String,
 }
 
+/// The parameters for `IssuingDispute::create`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct CreateIssuingDispute<'a> {
+    /// The dispute amount in the card's currency and in the [smallest currency unit](https://stripe.com/docs/currencies#zero-decimal).
+    ///
+    /// If not set, defaults to the full transaction amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+
+    /// Evidence provided for the dispute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<IssuingDisputeEvidenceParams<'a>>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// The ID of the issuing transaction to create a dispute for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<IssuingTransactionId>,
+}
+
+impl<'a> CreateIssuingDispute<'a> {
+    pub fn new() -> Self {
+        CreateIssuingDispute {
+            amount: Default::default(),
+            evidence: Default::default(),
+            expand: Default::default(),
+            metadata: Default::default(),
+            transaction: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `IssuingDispute::list`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ListIssuingDisputes<'a> {
+    /// Only return disputes that were created during the given date interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<RangeQuery<Timestamp>>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `ending_before` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, starting with `obj_bar`, your subsequent call can include `ending_before=obj_bar` in order to fetch the previous page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<IssuingDisputeId>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// A limit on the number of objects to be returned.
+    ///
+    /// Limit can range between 1 and 100, and the default is 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `starting_after` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, ending with `obj_foo`, your subsequent call can include `starting_after=obj_foo` in order to fetch the next page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<IssuingDisputeId>,
+
+    /// Select Issuing disputes with the given status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssuingDisputeStatus>,
+
+    /// Select the Issuing dispute for the given transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<IssuingTransactionId>,
+}
+
+impl<'a> ListIssuingDisputes<'a> {
+    pub fn new() -> Self {
+        ListIssuingDisputes {
+            created: Default::default(),
+            ending_before: Default::default(),
+            expand: Default::default(),
+            limit: Default::default(),
+            starting_after: Default::default(),
+            status: Default::default(),
+            transaction: Default::default(),
+        }
+    }
+}
+impl Paginable for ListIssuingDisputes<'_> {
+    type O = IssuingDispute;
+    fn set_last(&mut self, item: Self::O) {
+        self.starting_after = Some(item.id());
+    }
+}
+
+/// The parameters for `IssuingDispute::update`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateIssuingDispute<'a> {
+    /// The dispute amount in the card's currency and in the [smallest currency unit](https://stripe.com/docs/currencies#zero-decimal).
+    ///
+    /// If not set, defaults to the full transaction amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+
+    /// Evidence provided for the dispute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<IssuingDisputeEvidenceParams<'a>>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<'a> UpdateIssuingDispute<'a> {
+    pub fn new() -> Self {
+        UpdateIssuingDispute {
+            amount: Default::default(),
+            evidence: Default::default(),
+            expand: Default::default(),
+            metadata: Default::default(),
+        }
+    }
+}
+
+/// The parameters for `IssuingDispute::submit`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct SubmitIssuingDispute<'a> {
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<'a> SubmitIssuingDispute<'a> {
+    pub fn new() -> Self {
+        SubmitIssuingDispute { expand: Default::default(), metadata: Default::default() }
+    }
+}
+
+/// Evidence provided when creating or updating a dispute.
+///
+/// Only the evidence matching `reason` is used.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IssuingDisputeEvidenceParams<'a> {
+    /// Evidence provided when `reason` is 'canceled'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canceled: Option<IssuingDisputeEvidenceDetails<'a>>,
+
+    /// Evidence provided when `reason` is 'duplicate'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate: Option<IssuingDisputeEvidenceDetails<'a>>,
+
+    /// Evidence provided when `reason` is 'fraudulent'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fraudulent: Option<IssuingDisputeEvidenceDetails<'a>>,
+
+    /// Evidence provided when `reason` is 'merchandise_not_as_described'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchandise_not_as_described: Option<IssuingDisputeEvidenceDetails<'a>>,
+
+    /// Evidence provided when `reason` is 'not_received'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_received: Option<IssuingDisputeEvidenceDetails<'a>>,
+
+    /// Evidence provided when `reason` is 'other'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other: Option<IssuingDisputeEvidenceDetails<'a>>,
+
+    /// The reason for filing the dispute.
+    ///
+    /// The evidence should be submitted in the field of the same name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<IssuingDisputeEvidenceReason>,
+
+    /// Evidence provided when `reason` is 'service_not_as_described'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_not_as_described: Option<IssuingDisputeEvidenceDetails<'a>>,
+}
+
+/// The evidence common to every dispute reason.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct IssuingDisputeEvidenceDetails<'a> {
+    /// (ID of a [file upload](https://stripe.com/docs/guides/file-upload)) Additional documentation supporting the dispute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_documentation: Option<FileId>,
+
+    /// Explanation of why the cardholder is disputing this transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<&'a str>,
+}
+
 /// An enum representing the possible values of an `IssuingDisputeCanceledEvidence`'s `product_type` field.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
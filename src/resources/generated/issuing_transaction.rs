@@ -4,8 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::ids::IssuingTransactionId;
-use crate::params::{Expandable, Metadata, Object, Timestamp};
+use crate::client::{Client, Response};
+use crate::ids::{IssuingCardId, IssuingCardholderId, IssuingTransactionId};
+use crate::params::{Expand, Expandable, List, Metadata, Object, Paginable, RangeQuery, Timestamp};
 use crate::resources::{
     BalanceTransaction, Currency, IssuingAuthorization, IssuingCard, IssuingCardholder,
     IssuingDispute, IssuingToken, IssuingTransactionType, MerchantData,
@@ -98,6 +99,39 @@ pub struct IssuingTransaction {
     pub wallet: Option<IssuingTransactionWallet>,
 }
 
+impl IssuingTransaction {
+    /// Returns a list of Issuing `Transaction` objects.
+    ///
+    /// The objects are sorted in descending order by creation date, with the most recently created object appearing first.
+    pub fn list(
+        client: &Client,
+        params: &ListIssuingTransactions<'_>,
+    ) -> Response<List<IssuingTransaction>> {
+        client.get_query("/issuing/transactions", params)
+    }
+
+    /// Retrieves an Issuing `Transaction` object.
+    pub fn retrieve(
+        client: &Client,
+        id: &IssuingTransactionId,
+        expand: &[&str],
+    ) -> Response<IssuingTransaction> {
+        client.get_query(&format!("/issuing/transactions/{}", id), Expand { expand })
+    }
+
+    /// Updates the specified Issuing `Transaction` object by setting the values of the parameters passed.
+    ///
+    /// Any parameters not provided will be left unchanged.
+    pub fn update(
+        client: &Client,
+        id: &IssuingTransactionId,
+        params: UpdateIssuingTransaction<'_>,
+    ) -> Response<IssuingTransaction> {
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        client.post_form(&format!("/issuing/transactions/{}", id), &params)
+    }
+}
+
 impl Object for IssuingTransaction {
     type Id = IssuingTransactionId;
     fn id(&self) -> Self::Id {
@@ -248,6 +282,97 @@ pub struct IssuingTransactionTreasury {
     pub received_debit: Option<String>,
 }
 
+/// The parameters for `IssuingTransaction::list`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ListIssuingTransactions<'a> {
+    /// Only return transactions that belong to the given card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<IssuingCardId>,
+
+    /// Only return transactions that belong to the given cardholder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cardholder: Option<IssuingCardholderId>,
+
+    /// Only return transactions that were created during the given date interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<RangeQuery<Timestamp>>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `ending_before` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, starting with `obj_bar`, your subsequent call can include `ending_before=obj_bar` in order to fetch the previous page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_before: Option<IssuingTransactionId>,
+
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// A limit on the number of objects to be returned.
+    ///
+    /// Limit can range between 1 and 100, and the default is 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// A cursor for use in pagination.
+    ///
+    /// `starting_after` is an object ID that defines your place in the list.
+    /// For instance, if you make a list request and receive 100 objects, ending with `obj_foo`, your subsequent call can include `starting_after=obj_foo` in order to fetch the next page of the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_after: Option<IssuingTransactionId>,
+
+    /// Only return transactions that have the given type.
+    ///
+    /// One of `capture` or `refund`.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<IssuingTransactionType>,
+}
+
+impl<'a> ListIssuingTransactions<'a> {
+    pub fn new() -> Self {
+        ListIssuingTransactions {
+            card: Default::default(),
+            cardholder: Default::default(),
+            created: Default::default(),
+            ending_before: Default::default(),
+            expand: Default::default(),
+            limit: Default::default(),
+            starting_after: Default::default(),
+            type_: Default::default(),
+        }
+    }
+}
+
+impl Paginable for ListIssuingTransactions<'_> {
+    type O = IssuingTransaction;
+    fn set_last(&mut self, item: Self::O) {
+        self.starting_after = Some(item.id());
+    }
+}
+
+/// The parameters for `IssuingTransaction::update`.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct UpdateIssuingTransaction<'a> {
+    /// Specifies which fields in the response should be expanded.
+    #[serde(skip_serializing_if = "Expand::is_empty")]
+    pub expand: &'a [&'a str],
+
+    /// Set of [key-value pairs](https://stripe.com/docs/api/metadata) that you can attach to an object.
+    ///
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Individual keys can be unset by posting an empty value to them.
+    /// All keys can be unset by posting an empty value to `metadata`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<'a> UpdateIssuingTransaction<'a> {
+    pub fn new() -> Self {
+        UpdateIssuingTransaction { expand: Default::default(), metadata: Default::default() }
+    }
+}
+
 /// An enum representing the possible values of an `IssuingTransaction`'s `wallet` field.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -0,0 +1,226 @@
+use crate::error::WebhookError;
+use crate::params::Metadata;
+use crate::resources::generated::core::version::VERSION;
+use crate::resources::{
+    Event, EventObject, EventType, IssuingAuthorization, IssuingAuthorizationPendingRequest,
+    IssuingCard, MerchantData,
+};
+
+/// A real-time authorization request, sent by the `issuing_authorization.request` webhook.
+///
+/// Stripe waits two seconds for the decision in the response to the webhook,
+/// which is built from [`approve`](Self::approve) or [`decline`](Self::decline).
+///
+/// ```no_run
+/// # use stripe::{AuthorizationRequest, MerchantCategory, Webhook};
+/// # fn handle(payload: &str, sig: &str, secret: &str) -> Result<(), stripe::WebhookError> {
+/// let event = Webhook::construct_event(payload, sig, secret)?;
+/// let request = AuthorizationRequest::from_event(event)?;
+///
+/// let decision = if request.merchant().category == MerchantCategory::BettingCasinoGambling
+///     || request.pending_request().amount > 50_000
+/// {
+///     request.decline()
+/// } else {
+///     request.approve()
+/// };
+/// // respond with status 200, `decision.headers()` and `decision.body()`
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AuthorizationRequest {
+    authorization: IssuingAuthorization,
+    pending_request: IssuingAuthorizationPendingRequest,
+    api_version: String,
+}
+
+impl AuthorizationRequest {
+    /// Get the authorization request from a verified event.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a WebhookError if:
+    ///  - the event is not an `issuing_authorization.request` event
+    ///  - the authorization has no pending request
+    pub fn from_event(event: Event) -> Result<Self, WebhookError> {
+        let authorization = match (event.type_, event.data.object) {
+            (EventType::IssuingAuthorizationRequest, EventObject::IssuingAuthorization(auth)) => {
+                auth
+            }
+            (type_, _) => return Err(WebhookError::BadEventType(type_)),
+        };
+        let pending_request =
+            authorization.pending_request.clone().ok_or(WebhookError::NotPending)?;
+        let api_version = event.api_version.unwrap_or_else(|| VERSION.to_string());
+        Ok(Self { authorization, pending_request, api_version })
+    }
+
+    /// The authorization waiting for a decision.
+    pub fn authorization(&self) -> &IssuingAuthorization {
+        &self.authorization
+    }
+
+    /// The amounts to be held, and whether the held amount can be changed.
+    pub fn pending_request(&self) -> &IssuingAuthorizationPendingRequest {
+        &self.pending_request
+    }
+
+    /// The seller the authorization is requested by.
+    pub fn merchant(&self) -> &MerchantData {
+        &self.authorization.merchant_data
+    }
+
+    /// The card the authorization is requested on.
+    pub fn card(&self) -> &IssuingCard {
+        &self.authorization.card
+    }
+
+    /// Approve the requested amount.
+    pub fn approve(&self) -> AuthorizationDecision {
+        self.decide(true, None)
+    }
+
+    /// Approve a part of the requested amount, for example the balance left on the card.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a WebhookError if:
+    ///  - the amount of the pending request is not controllable
+    ///  - the given amount is not positive, or more than the requested amount
+    pub fn approve_amount(&self, amount: i64) -> Result<AuthorizationDecision, WebhookError> {
+        if !self.pending_request.is_amount_controllable {
+            return Err(WebhookError::AmountNotControllable);
+        }
+        if amount <= 0 || amount > self.pending_request.amount {
+            return Err(WebhookError::BadAmount(amount));
+        }
+        Ok(self.decide(true, Some(amount)))
+    }
+
+    /// Decline the authorization.
+    pub fn decline(&self) -> AuthorizationDecision {
+        self.decide(false, None)
+    }
+
+    fn decide(&self, approved: bool, amount: Option<i64>) -> AuthorizationDecision {
+        AuthorizationDecision {
+            approved,
+            amount,
+            metadata: None,
+            api_version: self.api_version.clone(),
+        }
+    }
+}
+
+/// The decision on an [`AuthorizationRequest`], to be sent as the response to its webhook
+/// with a `200` status.
+#[derive(Clone, Debug)]
+pub struct AuthorizationDecision {
+    approved: bool,
+    amount: Option<i64>,
+    metadata: Option<Metadata>,
+    api_version: String,
+}
+
+impl AuthorizationDecision {
+    /// Set metadata on the authorization along with the decision.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Whether the authorization is approved.
+    pub fn approved(&self) -> bool {
+        self.approved
+    }
+
+    /// The headers of the response.
+    pub fn headers(&self) -> [(&'static str, &str); 2] {
+        [("Content-Type", "application/json"), ("Stripe-Version", &self.api_version)]
+    }
+
+    /// The json body of the response.
+    pub fn body(&self) -> String {
+        let mut body = serde_json::json!({ "approved": self.approved });
+        if let Some(amount) = self.amount {
+            body["amount"] = amount.into();
+        }
+        if let Some(metadata) = &self.metadata {
+            body["metadata"] = serde_json::json!(metadata);
+        }
+        body.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuthorizationRequest;
+    use crate::resources::{
+        Event, EventObject, EventType, IssuingAuthorization, IssuingAuthorizationPendingRequest,
+    };
+    use crate::WebhookError;
+
+    fn event(controllable: bool) -> Event {
+        let mut event = Event {
+            type_: EventType::IssuingAuthorizationRequest,
+            api_version: Some("2023-10-16".to_string()),
+            ..Default::default()
+        };
+        event.data.object = EventObject::IssuingAuthorization(IssuingAuthorization {
+            pending_request: Some(IssuingAuthorizationPendingRequest {
+                amount: 1000,
+                is_amount_controllable: controllable,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        event
+    }
+
+    #[test]
+    fn decisions() {
+        let request = AuthorizationRequest::from_event(event(true)).unwrap();
+        assert_eq!(request.pending_request().amount, 1000);
+
+        let approve = request.approve();
+        assert_eq!(approve.body(), r#"{"approved":true}"#);
+        assert_eq!(
+            approve.headers(),
+            [("Content-Type", "application/json"), ("Stripe-Version", "2023-10-16")]
+        );
+
+        let partial = request
+            .approve_amount(400)
+            .unwrap()
+            .with_metadata([("rule".to_string(), "balance".to_string())].into());
+        assert_eq!(
+            partial.body(),
+            r#"{"amount":400,"approved":true,"metadata":{"rule":"balance"}}"#
+        );
+        assert!(matches!(request.approve_amount(0), Err(WebhookError::BadAmount(0))));
+        assert!(matches!(request.approve_amount(1001), Err(WebhookError::BadAmount(1001))));
+        assert!(request.approve_amount(1000).is_ok());
+
+        assert_eq!(request.decline().body(), r#"{"approved":false}"#);
+
+        let fixed = AuthorizationRequest::from_event(event(false)).unwrap();
+        assert!(matches!(fixed.approve_amount(400), Err(WebhookError::AmountNotControllable)));
+    }
+
+    #[test]
+    fn other_events() {
+        let mut created = event(true);
+        created.type_ = EventType::IssuingAuthorizationCreated;
+        assert!(matches!(
+            AuthorizationRequest::from_event(created),
+            Err(WebhookError::BadEventType(EventType::IssuingAuthorizationCreated))
+        ));
+
+        let mut closed = event(true);
+        if let EventObject::IssuingAuthorization(auth) = &mut closed.data.object {
+            auth.pending_request = None;
+        }
+        assert!(matches!(AuthorizationRequest::from_event(closed), Err(WebhookError::NotPending)));
+    }
+}
@@ -11,6 +11,8 @@ use smart_default::SmartDefault;
 
 use crate::error::WebhookError;
 use crate::resources::*;
+#[cfg(feature = "webhook-events")]
+use crate::Mode;
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Hash, SmartDefault)]
 pub enum EventType {
@@ -505,6 +507,28 @@ impl Webhook {
         Self { current_timestamp: Utc::now().timestamp() }.do_construct_event(payload, sig, secret)
     }
 
+    /// Construct an event from a webhook payload and signature, also checking that
+    /// it was sent in the expected mode.
+    ///
+    /// This flags events delivered to the wrong deployment, such as live events
+    /// sent to a staging environment.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a WebhookError if:
+    ///  - the event cannot be constructed, see [`construct_event`](Self::construct_event)
+    ///  - the `livemode` of the event does not match the expected mode
+    pub fn construct_event_in_mode(
+        payload: &str,
+        sig: &str,
+        secret: &str,
+        mode: Mode,
+    ) -> Result<Event, WebhookError> {
+        let event = Self::construct_event(payload, sig, secret)?;
+        Self::check_mode(&event, mode)?;
+        Ok(event)
+    }
+
     /// Construct an event from a webhook payload and signature, verifying its signature
     /// using the provided timestamp.
     ///
@@ -553,6 +577,13 @@ impl Webhook {
 
         Ok(serde_json::from_str(payload)?)
     }
+
+    fn check_mode(event: &Event, mode: Mode) -> Result<(), WebhookError> {
+        if event.livemode != mode.is_live() {
+            return Err(WebhookError::BadMode(mode));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(feature = "webhook-events")]
@@ -663,4 +694,34 @@ mod tests {
         assert_eq!(event.account, "acct_123".parse().ok());
         assert_eq!(event.created, 1533204620);
     }
+
+    #[cfg(feature = "webhook-events")]
+    #[test]
+    fn test_webhook_construct_event_in_mode() {
+        use crate::{Mode, WebhookError};
+
+        let event = |livemode: bool| -> super::Event {
+            serde_json::from_value(serde_json::json!({
+                "id": "evt_123",
+                "object": "event",
+                "created": 1533204620,
+                "data": { "object": { "id": "bal_123", "object": "balance", "available": [], "pending": [], "livemode": livemode } },
+                "livemode": livemode,
+                "pending_webhooks": 1,
+                "type": "balance.available"
+            }))
+            .expect("Failed to parse event")
+        };
+
+        assert!(super::Webhook::check_mode(&event(false), Mode::Test).is_ok());
+        assert!(super::Webhook::check_mode(&event(true), Mode::Live).is_ok());
+        assert!(matches!(
+            super::Webhook::check_mode(&event(false), Mode::Live),
+            Err(WebhookError::BadMode(Mode::Live))
+        ));
+        assert!(matches!(
+            super::Webhook::check_mode(&event(true), Mode::Test),
+            Err(WebhookError::BadMode(Mode::Test))
+        ));
+    }
 }
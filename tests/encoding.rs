@@ -313,3 +313,127 @@ fn deserialize_checkout_event() {
     let result = serde_json::from_value::<Event>(example);
     assert!(result.is_ok(), "expected ok; was {:?}", result);
 }
+
+#[test]
+#[cfg(feature = "issuing")]
+fn serialize_create_issuing_card() {
+    use stripe::{
+        Address, CreateIssuingCard, CreateIssuingCardShipping, Currency, IssuingCardShippingType,
+        IssuingCardSpendingControls, IssuingCardSpendingLimit, IssuingCardSpendingLimitInterval,
+        IssuingCardType, MerchantCategory,
+    };
+
+    let params = CreateIssuingCard {
+        shipping: Some(CreateIssuingCardShipping {
+            address: Address {
+                city: Some("Berlin".to_string()),
+                country: Some("DE".to_string()),
+                line1: Some("Unter den Linden 1".to_string()),
+                postal_code: Some("10117".to_string()),
+                ..Default::default()
+            },
+            name: "Jenny Rosen",
+            phone_number: None,
+            require_signature: None,
+            service: None,
+            type_: Some(IssuingCardShippingType::Individual),
+        }),
+        spending_controls: Some(IssuingCardSpendingControls {
+            blocked_categories: Some(vec![MerchantCategory::BettingCasinoGambling]),
+            spending_limits: Some(vec![IssuingCardSpendingLimit {
+                amount: 5000,
+                categories: None,
+                interval: IssuingCardSpendingLimitInterval::Daily,
+            }]),
+            ..Default::default()
+        }),
+        ..CreateIssuingCard::new(Currency::EUR, IssuingCardType::Virtual)
+    };
+    assert_eq!(
+        urldecode(serde_qs::to_string(&params).unwrap()),
+        concat!(
+            "currency=eur",
+            "&shipping[address][city]=Berlin&shipping[address][country]=DE",
+            "&shipping[address][line1]=Unter+den+Linden+1&shipping[address][postal_code]=10117",
+            "&shipping[name]=Jenny+Rosen&shipping[type]=individual",
+            "&spending_controls[blocked_categories][0]=betting_casino_gambling",
+            "&spending_controls[spending_limits][0][amount]=5000",
+            "&spending_controls[spending_limits][0][interval]=daily",
+            "&type=virtual",
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "issuing")]
+fn serialize_create_issuing_cardholder() {
+    use stripe::{
+        Address, CreateIssuingCardholder, Currency, IssuingCardholderAddress,
+        IssuingCardholderSpendingControls, IssuingCardholderSpendingLimit,
+        IssuingCardholderSpendingLimitInterval, IssuingCardholderType, MerchantCategory,
+    };
+
+    let billing = IssuingCardholderAddress {
+        address: Address {
+            city: Some("Berlin".to_string()),
+            country: Some("DE".to_string()),
+            line1: Some("Unter den Linden 1".to_string()),
+            postal_code: Some("10117".to_string()),
+            ..Default::default()
+        },
+    };
+    let params = CreateIssuingCardholder {
+        email: Some("jenny@example.com"),
+        spending_controls: Some(IssuingCardholderSpendingControls {
+            allowed_categories: Some(vec![MerchantCategory::BettingCasinoGambling]),
+            spending_limits: Some(vec![IssuingCardholderSpendingLimit {
+                amount: 10000,
+                categories: None,
+                interval: IssuingCardholderSpendingLimitInterval::Monthly,
+            }]),
+            spending_limits_currency: Some(Currency::EUR),
+            ..Default::default()
+        }),
+        type_: Some(IssuingCardholderType::Individual),
+        ..CreateIssuingCardholder::new(billing, "Jenny Rosen")
+    };
+    assert_eq!(
+        urldecode(serde_qs::to_string(&params).unwrap()),
+        concat!(
+            "billing[address][city]=Berlin&billing[address][country]=DE",
+            "&billing[address][line1]=Unter+den+Linden+1&billing[address][postal_code]=10117",
+            "&email=jenny%40example.com&name=Jenny+Rosen",
+            "&spending_controls[allowed_categories][0]=betting_casino_gambling",
+            "&spending_controls[spending_limits][0][amount]=10000",
+            "&spending_controls[spending_limits][0][interval]=monthly",
+            "&spending_controls[spending_limits_currency]=eur",
+            "&type=individual",
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "issuing")]
+fn serialize_issuing_dispute_evidence() {
+    use stripe::{
+        IssuingDisputeEvidenceDetails, IssuingDisputeEvidenceParams, IssuingDisputeEvidenceReason,
+        UpdateIssuingDispute,
+    };
+
+    let params = UpdateIssuingDispute {
+        amount: Some(500),
+        evidence: Some(IssuingDisputeEvidenceParams {
+            reason: Some(IssuingDisputeEvidenceReason::NotReceived),
+            not_received: Some(IssuingDisputeEvidenceDetails {
+                explanation: Some("never arrived"),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..UpdateIssuingDispute::new()
+    };
+    assert_eq!(
+        urldecode(serde_qs::to_string(&params).unwrap()),
+        "amount=500&evidence[not_received][explanation]=never+arrived&evidence[reason]=not_received"
+    );
+}